@@ -0,0 +1,201 @@
+//! Decoding of the airline individual use data which ends each leg, whose layout
+//! is chosen by the operating carrier.
+
+use std::collections::HashMap;
+
+use super::designator::AirlineDesignator;
+use super::{raw, Leg};
+
+/// A value decoded from the airline individual use data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub enum AirlineValue {
+    Text(String),
+    Number(u32),
+    Char(char),
+}
+
+/// The named values decoded from the airline individual use data of a leg, in the
+/// order the decoder produced them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub struct AirlineData {
+    fields: Vec<(String, AirlineValue)>,
+}
+
+impl AirlineData {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the value `value` named `key`.
+    pub fn push(&mut self, key: &str, value: AirlineValue) {
+        self.fields.push((key.to_owned(), value));
+    }
+
+    /// The first value named `key`.
+    pub fn get(&self, key: &str) -> Option<&AirlineValue> {
+        self.fields.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &AirlineValue)> {
+        self.fields.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+/// Decodes the airline individual use data of a carrier.
+///
+/// Any function taking the data and returning `Option<AirlineData>` is a decoder.
+pub trait AirlineUseDecoder: Send + Sync {
+
+    /// Decodes `data`, or returns `None` if it does not follow the layout of the decoder.
+    fn decode(&self, data: &str) -> Option<AirlineData>;
+}
+
+impl<F> AirlineUseDecoder for F
+where
+    F: Fn(&str) -> Option<AirlineData> + Send + Sync
+{
+    fn decode(&self, data: &str) -> Option<AirlineData> {
+        self(data)
+    }
+}
+
+/// Decoders of the airline individual use data, registered per operating carrier.
+///
+/// ```
+/// use iata::bcbp::{AirlineUseRegistry, AirlineValue, raw};
+///
+/// let registry = AirlineUseRegistry::with_reference_decoders();
+/// let pass = raw::Bcbp::from("M1MROZ/MARTIN         EXXXXXX SJCLAXAS 3317 207U001A0006 34D>218 VV8207BAS              2502771980993865 AS AS XXXXX55200000000Z29  00010").unwrap();
+/// let data = registry.decode_raw(&pass.legs()[0]).unwrap();
+///
+/// assert_eq!(data.get("sequence"), Some(&AirlineValue::Number(10)));
+/// ```
+#[derive(Default)]
+pub struct AirlineUseRegistry {
+    decoders: HashMap<AirlineDesignator, Box<dyn AirlineUseDecoder>>,
+}
+
+impl AirlineUseRegistry {
+
+    /// Constructs a registry without any decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a registry with the reference decoders, [`AlaskaDecoder`] and
+    /// [`StarAllianceDecoder`] for the carriers whose passes they were derived from.
+    pub fn with_reference_decoders() -> Self {
+        let mut registry = Self::new();
+
+        registry.register(designator("AS"), AlaskaDecoder);
+
+        for carrier in ["AC", "LH", "UA"] {
+            registry.register(designator(carrier), StarAllianceDecoder);
+        }
+
+        registry
+    }
+
+    /// Registers the `decoder` of the operating `carrier`, replacing the previous one.
+    pub fn register<D>(&mut self, carrier: AirlineDesignator, decoder: D)
+    where
+        D: AirlineUseDecoder + 'static
+    {
+        self.decoders.insert(carrier, Box::new(decoder));
+    }
+
+    /// The decoder of the operating `carrier`.
+    pub fn get(&self, carrier: AirlineDesignator) -> Option<&dyn AirlineUseDecoder> {
+        self.decoders.get(&carrier).map(Box::as_ref)
+    }
+
+    /// Decodes `data` with the decoder of the operating `carrier`.
+    pub fn decode_with(&self, carrier: AirlineDesignator, data: &str) -> Option<AirlineData> {
+        self.get(carrier)?.decode(data)
+    }
+
+    /// Decodes the airline individual use data of the `leg` with the decoder of its operating carrier.
+    pub fn decode(&self, leg: &Leg) -> Option<AirlineData> {
+        self.decode_with(leg.airline()?, leg.var.as_deref()?)
+    }
+
+    /// Decodes the airline individual use data of the `leg` with the decoder of its operating carrier.
+    pub fn decode_raw(&self, leg: &raw::Leg) -> Option<AirlineData> {
+        self.decode_with(leg.airline().parse().ok()?, leg.airline_individual_use()?)
+    }
+}
+
+fn designator(src: &str) -> AirlineDesignator {
+    // Only called with valid designators.
+    src.parse().unwrap()
+}
+
+/// Decodes the data of Alaska Airlines passes, such as `Z29  00010`, into
+/// the `code` and the 5-digit `sequence` which ends it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlaskaDecoder;
+
+impl AirlineUseDecoder for AlaskaDecoder {
+    fn decode(&self, data: &str) -> Option<AirlineData> {
+
+        if data.len() != 10 || !data.is_ascii() {
+            return None
+        }
+
+        let (code, sequence) = data.split_at(5);
+
+        if !sequence.bytes().all(|x| x.is_ascii_digit()) {
+            return None
+        }
+
+        let mut ret = AirlineData::new();
+        ret.push("code", AirlineValue::Text(code.trim_end().to_owned()));
+        ret.push("sequence", AirlineValue::Number(sequence.parse().ok()?));
+
+        Some(ret)
+    }
+}
+
+/// Decodes the data of Star Alliance carriers, which starts with `*` and the
+/// digit of its `format_version`, as in `*30600    09  UAG    `.
+///
+/// The version 3 carries the 3-character `frequent_flyer_tier`, the designator of the
+/// frequent flyer program followed by the tier, such as `UAG`. The `data` following
+/// the version is kept as it is, without the trailing spaces.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StarAllianceDecoder;
+
+impl AirlineUseDecoder for StarAllianceDecoder {
+    fn decode(&self, data: &str) -> Option<AirlineData> {
+
+        let rest = data.strip_prefix('*')?;
+        let version = rest.chars().next()?.to_digit(10)?;
+        let rest = &rest[1..];
+
+        let mut ret = AirlineData::new();
+        ret.push("format_version", AirlineValue::Number(version));
+
+        if version == 3 {
+            let tier = rest.get(12..15).map(str::trim).unwrap_or("");
+
+            if !tier.is_empty() {
+                ret.push("frequent_flyer_tier", AirlineValue::Text(tier.to_owned()));
+            }
+        }
+
+        ret.push("data", AirlineValue::Text(rest.trim_end().to_owned()));
+
+        Some(ret)
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::{Error, ErrorKind, BcbpResult};
+
+/// The leading digit of a baggage tag license plate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BagTagKind {
+    Interline,      // 0
+    FallBack,       // 1
+    InterlineRush,  // 2
+    Other(char),
+}
+
+impl BagTagKind {
+    pub fn from_char(t: char) -> Self {
+        use BagTagKind::*;
+        match t {
+            '0' => Interline,
+            '1' => FallBack,
+            '2' => InterlineRush,
+            _   => Other(t)
+        }
+    }
+
+    pub fn to_char(&self) -> char {
+        use BagTagKind::*;
+        match *self {
+            Interline     => '0',
+            FallBack      => '1',
+            InterlineRush => '2',
+            Other(t)      => t
+        }
+    }
+}
+
+/// A series of consecutive baggage tag license plates, as encoded in the items 23, 31 and 32.
+///
+/// The 13 digits are divided into:
+///         0: leading digit, see [`BagTagKind`].
+///    1... 3: carrier numeric code.
+///    4... 9: carrier initial tag number with leading zeroes.
+///   10...12: number of bags in the series (up to 999).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "BagTagRangeRepr"))]
+pub struct BagTagRange {
+    kind: BagTagKind,
+    carrier: u16,
+    serial: u32,
+    count: u16,
+}
+
+/// The fields of a [`BagTagRange`] as they are deserialized, before validation.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+struct BagTagRangeRepr {
+    kind: BagTagKind,
+    carrier: u16,
+    serial: u32,
+    count: u16,
+}
+
+#[cfg(feature = "with-serde")]
+impl TryFrom<BagTagRangeRepr> for BagTagRange {
+    type Error = Error;
+
+    fn try_from(repr: BagTagRangeRepr) -> Result<Self, Self::Error> {
+        Self::new(repr.kind, repr.carrier, repr.serial, repr.count)
+    }
+}
+
+impl BagTagRange {
+
+    /// Constructs a series of `count` tags starting with `serial`.
+    ///
+    /// # Errors
+    /// * [`ErrorKind::DigitsExpected`] if the leading digit is not a digit.
+    /// * [`ErrorKind::MandatoryDataSize`] if `carrier`, `serial` or `count` do not fit
+    ///   their 3, 6 and 3 digits, or if the series runs past the last serial number.
+    pub fn new(kind: BagTagKind, carrier: u16, serial: u32, count: u16) -> BcbpResult<Self> {
+
+        if !kind.to_char().is_ascii_digit() {
+            return Err(ErrorKind::DigitsExpected.into())
+        }
+
+        if carrier > 999 || count > 999 || serial > 999_999 ||
+            serial + u32::from(count.max(1)) - 1 > 999_999
+        {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        Ok(Self { kind, carrier, serial, count })
+    }
+
+    /// The leading digit of the tags.
+    pub fn kind(&self) -> BagTagKind {
+        self.kind
+    }
+
+    /// The three-digit numeric code of the carrier which issued the tags.
+    pub fn carrier(&self) -> u16 {
+        self.carrier
+    }
+
+    /// The six-digit serial number of the first tag in the series.
+    pub fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    /// The number of bags in the series, as encoded.
+    /// Passes before version 6 write `000` for a single bag, see [`Self::tags()`].
+    pub fn count(&self) -> u16 {
+        self.count
+    }
+
+    /// Iterates over the 10-digit license plate of every tag in the series.
+    /// A count of `000` stands for the first tag alone.
+    ///
+    /// ```
+    /// use iata::bcbp::BagTagRange;
+    ///
+    /// let range: BagTagRange = "0016111111002".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     range.tags().collect::<Vec<_>>(),
+    ///     vec!["0016111111", "0016111112"],
+    /// );
+    /// ```
+    pub fn tags(&self) -> impl Iterator<Item = String> {
+        let range = *self;
+
+        (0..u32::from(range.count.max(1))).map(move |idx| {
+            format!("{}{:03}{:06}", range.kind.to_char(), range.carrier, range.serial + idx)
+        })
+    }
+
+    /// Checks if the 10-digit license plate `tag` belongs to the series.
+    pub fn contains(&self, tag: &str) -> bool {
+        self.tags().any(|x| x == tag)
+    }
+}
+
+impl fmt::Display for BagTagRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{:03}{:06}{:03}", self.kind.to_char(), self.carrier, self.serial, self.count)
+    }
+}
+
+impl FromStr for BagTagRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        if s.len() != 13 {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if !s.as_bytes().iter().all(u8::is_ascii_digit) {
+            return Err(ErrorKind::DigitsExpected.into())
+        }
+
+        // All characters are ASCII digits, so the slices and the numbers are valid.
+        Self::new(
+            BagTagKind::from_char(s.as_bytes()[0] as char),
+            s[1..4].parse().unwrap(),
+            s[4..10].parse().unwrap(),
+            s[10..13].parse().unwrap(),
+        )
+    }
+}
@@ -1,23 +1,139 @@
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
 use log::*;
 
 use super::{
-//     raw,
 //     field,
-    error::{Error, BcbpResult}
+    Mode,
+    raw::Offsets,
+    error::{Error, ErrorKind, BcbpResult},
+    diagnostic::{Diagnostic, Severity},
 };
 
-use super::field::Field;
+use super::field::{Field, DataType};
+
+/// Replaces each byte of the characters outside of ASCII when diagnosing,
+/// so that the input can be scanned and the offsets are preserved.
+pub(crate) const SUBSTITUTE: char = '\u{1A}';
 
 // #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub(crate) struct Chunk<'a> {
     input: &'a str,
+    /// Offset of the `input` in the whole boarding pass.
+    offset: usize,
+    mode: Mode,
+    /// Index of the leg being scanned, reported with the diagnostics.
+    leg: Option<usize>,
+    /// Problems are collected here instead of failing the scan, if set.
+    diagnostics: Option<Rc<RefCell<Vec<Diagnostic>>>>,
 }
 
 impl<'a> Chunk<'a> {
 
     /// Return a new intance of the receiver over the `input`.
     pub fn new(input: &'a str) -> Self {
-        Self { input }
+        Self::with_mode(input, Mode::Tolerant)
+    }
+
+    /// Return a new intance of the receiver over the `input`.
+    /// In `Mode::Strict` every scanned field is validated against its data type.
+    pub fn with_mode(input: &'a str, mode: Mode) -> Self {
+        Self { input, offset: 0, mode, leg: None, diagnostics: None }
+    }
+
+    /// Return a new intance of the receiver over the `input`, which collects the
+    /// problems it finds into `diagnostics` and only fails when it cannot go any further.
+    pub fn with_diagnostics(input: &'a str, diagnostics: Rc<RefCell<Vec<Diagnostic>>>) -> Self {
+        Self { input, offset: 0, mode: Mode::Tolerant, leg: None, diagnostics: Some(diagnostics) }
+    }
+
+    /// Consumes the next `len` bytes into a chunk sharing the settings of the receiver.
+    fn split(&mut self, len: usize) -> Chunk<'a> {
+        let sub = Self {
+            input: &self.input[..len],
+            offset: self.offset,
+            mode: self.mode,
+            leg: self.leg,
+            diagnostics: self.diagnostics.clone(),
+        };
+        self.input = &self.input[len..];
+        self.offset += len;
+        sub
+    }
+
+    /// Returns the offset of the remaining input in the whole boarding pass.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Sets the index of the leg the following fields belong to.
+    pub fn set_leg(&mut self, leg: Option<usize>) {
+        self.leg = leg;
+    }
+
+    /// Returns `true` if the receiver collects diagnostics rather than failing.
+    pub fn collects(&self) -> bool {
+        self.diagnostics.is_some()
+    }
+
+    /// Reports a problem with the `field` at `range`, which can be scanned past.
+    /// Fails with `error` unless diagnostics are collected, or the problem is a
+    /// [`Severity::Warning`] in `Mode::Tolerant`.
+    pub fn report(&self, field: Field, range: Range<usize>, error: Error, severity: Severity) -> BcbpResult<()> {
+        let error = self.locate(field, &range, error);
+
+        if self.diagnostics.is_some() {
+            self.push(field, range, error, severity);
+            return Ok(())
+        }
+
+        match (severity, self.mode) {
+            (Severity::Warning, Mode::Tolerant) => Ok(()),
+            _ => Err(error),
+        }
+    }
+
+    /// Reports a problem with the `field` at `range`, which prevents scanning any further.
+    /// Returns the `error` to fail with.
+    pub fn fail(&self, field: Field, range: Range<usize>, error: Error) -> Error {
+        let error = self.locate(field, &range, error);
+
+        if self.diagnostics.is_some() {
+            self.push(field, range, error.clone(), Severity::Error);
+        }
+        error
+    }
+
+    /// Completes the `error` with the position of the `field`.
+    fn locate(&self, field: Field, range: &Range<usize>, error: Error) -> Error {
+        error
+            .with_field(field)
+            .with_offset(range.start)
+            .with_leg(self.leg)
+    }
+
+    /// Records a diagnostic. An item is reported once, with its most serious problem.
+    fn push(&self, field: Field, range: Range<usize>, error: Error, severity: Severity) {
+        trace!("Diagnostic {:?} {} at {:?} - {:?}", severity, field, range, error);
+
+        if let Some(ref diagnostics) = self.diagnostics {
+            let mut diagnostics = diagnostics.borrow_mut();
+
+            let previous = diagnostics
+                .iter()
+                .position(|x| x.field == field && x.leg == self.leg && x.range == range);
+
+            match previous.map(|idx| (idx, diagnostics[idx].severity)) {
+                Some((_, Severity::Error))     => return,
+                Some((idx, Severity::Warning)) => { diagnostics.remove(idx); }
+                None                           => {}
+            }
+
+            diagnostics.push(Diagnostic { field, leg: self.leg, range, severity, error });
+        }
     }
 
     /// Returns `true` if no more input is available.
@@ -32,24 +148,51 @@ impl<'a> Chunk<'a> {
         self.input.len()
     }
 
+    /// Returns the remaining input without consuming it.
+    #[inline]
+    pub fn rest(&self) -> &'a str {
+        self.input
+    }
+
     /// Returns a chunk over a fixed-length sub-section of the input.
     /// The entire amount is consumed immediately if space is available whether or not
     /// any fields within the sub-section are invalid.
+    /// `field` is the size field which declared the length, scanned right before.
     ///
     /// # Panics
     /// Will panic if `len` is `0`.
-    pub fn fetch_chunk(&mut self, len: usize) -> BcbpResult<Chunk<'a>> {
+    pub fn fetch_chunk(&mut self, field: Field, len: usize) -> BcbpResult<Chunk<'a>> {
         assert!(
             len > 0,
             "Attempting to scan a zero-length sub-field list is not valid."
         );
         trace!("Scanning Subsection (Length {})", len);
         if self.len() < len {
-            Err(Error::SubsectionTooLong)
+            let range = self.offset.saturating_sub(field.len())..self.offset;
+            Err(self.fail(field, range, ErrorKind::SubsectionTooLong.into()))
         } else {
-            let sub_fields = &self.input[..len];
-            self.input = &self.input[len..];
-            Ok(Self::new(sub_fields))
+            Ok(self.split(len))
+        }
+    }
+
+    /// Returns a chunk over the next `len` bytes of the input, or over the rest of it
+    /// when less is available. The returned amount is consumed immediately.
+    pub fn fetch_chunk_upto(&mut self, len: usize) -> Chunk<'a> {
+        let len = len.min(self.len());
+        trace!("Scanning Subsection (Up To Length {})", len);
+        self.split(len)
+    }
+
+    /// Consumes and returns the rest of the input, if any.
+    pub fn fetch_rest(&mut self) -> Option<&'a str> {
+        if self.eof() {
+            None
+        } else {
+            let rest = self.input;
+            self.input = "";
+            self.offset += rest.len();
+            trace!("Scanning Remaining Data - '{}'", rest);
+            Some(rest)
         }
     }
 
@@ -71,15 +214,72 @@ impl<'a> Chunk<'a> {
                 field,
                 len
             );
-            Err(Error::UnexpectedEndOfInput(field))
+            let range = self.offset..self.offset + self.len();
+            Err(self.fail(field, range, ErrorKind::UnexpectedEndOfInput.into()))
         } else {
+            let range = self.offset..self.offset + len;
             let substring = &self.input[..len];
             self.input = &self.input[len..];
+            self.offset += len;
             trace!("Scanning {} (Length {}) - '{}'", field, len, substring);
+            if self.collects() && substring.contains(SUBSTITUTE) {
+                self.report(field, range, ErrorKind::InvalidCharacters.into(), Severity::Error)?;
+            } else if self.mode == Mode::Strict || self.collects() {
+                if let Err(error) = Self::verify(field, substring) {
+                    self.report(field, range, error, Severity::Warning)?;
+                }
+            }
             Ok(substring)
         }
     }
 
+    /// Checks `value` against the data type and the padding of the `field`.
+    fn verify(field: Field, value: &str) -> BcbpResult<()> {
+        let data_type = field.data_type();
+
+        if data_type.is_valid(value) {
+            if field.is_padding_valid(value) {
+                return Ok(())
+            }
+
+            trace!("Invalid Padding {} - '{}'", field, value);
+            return Err(ErrorKind::InvalidCharacters.into())
+        }
+
+        trace!("Invalid {} - '{}'", field, value);
+
+        let kind = match data_type {
+            DataType::Numeric     => ErrorKind::DigitsExpected,
+            DataType::Alphabetic  => ErrorKind::AlphaExpected,
+            DataType::Hexadecimal => ErrorKind::ExpectedInteger,
+            DataType::Flexible |
+            DataType::Unspecified => ErrorKind::InvalidCharacters,
+        };
+
+        Err(kind.into())
+    }
+
+    /// Passes the `value` of the `field` to `store`. A value `store` rejects is reported
+    /// with the `severity` at the offset recorded for the `field` in `offsets` when it was
+    /// scanned, or without a position if it was not.
+    pub fn check<F>(&self, field: Field, offsets: &Offsets, value: &str, severity: Severity, store: F) -> BcbpResult<()>
+    where
+        F: FnOnce(&str) -> BcbpResult<()>
+    {
+        let error = match store(value) {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+
+        match offsets.get(&field) {
+            Some(&start) => self.report(field, start..start + value.len(), error, severity),
+            None => match (severity, self.mode) {
+                (Severity::Warning, Mode::Tolerant) => Ok(()),
+                _ => Err(error.with_field(field).with_leg(self.leg)),
+            },
+        }
+    }
+
     /// Scans and returns the string underlying a fixed-length field.
     /// Uses the intrinsic length.
     ///
@@ -150,8 +350,10 @@ impl<'a> Chunk<'a> {
     /// # Issues
     /// Should not advance the input until the numeric value is sucessfully scanned.
     pub fn fetch_usize(&mut self, field: Field, radix: u32) -> BcbpResult<usize> {
+        let start = self.offset;
         self.fetch_str(field).and_then(|str_value| {
-            usize::from_str_radix(str_value, radix).map_err(|_| Error::ExpectedInteger(field))
+            usize::from_str_radix(str_value, radix)
+                .map_err(|_| self.fail(field, start..self.offset, ErrorKind::ExpectedInteger.into()))
         })
     }
 }
@@ -0,0 +1,143 @@
+//! Single character items whose values are defined by the code tables of Resolution 792.
+
+macro_rules! code_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident = $code:literal => $description:literal, )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum $name {
+            /// Blank, the item is not set.
+            #[default]
+            None,
+            $( $(#[$variant_meta])* $variant, )*
+            /// A value the code table does not define.
+            Other(char),
+        }
+
+        impl $name {
+            pub fn from_char(t: char) -> Self {
+                match t {
+                    ' '   => $name::None,
+                    $( $code => $name::$variant, )*
+                    _     => $name::Other(t)
+                }
+            }
+
+            pub fn to_char(&self) -> char {
+                match *self {
+                    $name::None     => ' ',
+                    $( $name::$variant => $code, )*
+                    $name::Other(t) => t
+                }
+            }
+
+            /// Meaning of the value, as given by Resolution 792.
+            pub fn description(&self) -> &'static str {
+                match *self {
+                    $name::None     => "Not set",
+                    $( $name::$variant => $description, )*
+                    $name::Other(_) => "Unknown"
+                }
+            }
+        }
+    };
+}
+
+code_enum! {
+    /// Item 253, Electronic Ticket Indicator.
+    pub enum TicketIndicator {
+        ElectronicTicket = 'E' => "Electronic ticket",
+        Ticketless       = 'L' => "Ticketless",
+    }
+}
+
+code_enum! {
+    /// Item 12, Source of Check-In.
+    pub enum CheckInSource {
+        Web              = 'W' => "Web",
+        AirportKiosk     = 'K' => "Airport kiosk",
+        RemoteKiosk      = 'R' => "Remote or off site kiosk",
+        MobileDevice     = 'M' => "Mobile device",
+        AirportAgent     = 'O' => "Airport agent",
+        TownAgent        = 'T' => "Town agent",
+        ThirdPartyVendor = 'V' => "Third party vendor",
+    }
+}
+
+code_enum! {
+    /// Item 14, Source of Boarding Pass Issuance.
+    pub enum BoardingPassSource {
+        WebPrinted       = 'W' => "Web printed",
+        AirportKiosk     = 'K' => "Airport kiosk",
+        TransferKiosk    = 'X' => "Transfer kiosk",
+        RemoteKiosk      = 'R' => "Remote or off site kiosk",
+        MobileDevice     = 'M' => "Mobile device",
+        AirportAgent     = 'O' => "Airport agent",
+        TownAgent        = 'T' => "Town agent",
+        ThirdPartyVendor = 'V' => "Third party vendor",
+    }
+}
+
+code_enum! {
+    /// Item 16, Document Type.
+    pub enum DocumentType {
+        BoardingPass     = 'B' => "Boarding pass",
+        ItineraryReceipt = 'I' => "Itinerary receipt",
+    }
+}
+
+code_enum! {
+    /// Item 18, Selectee Indicator. Required when travel involves the United States.
+    pub enum SelecteeIndicator {
+        NotSelectee    = '0' => "Not selectee",
+        Selectee       = '1' => "Selectee",
+        KnownPassenger = '3' => "Known passenger",
+    }
+}
+
+code_enum! {
+    /// Item 108, International Documentation Verification.
+    pub enum DocumentVerification {
+        NotRequired = '0' => "Travel document verification not required",
+        Required    = '1' => "Travel document verification required",
+        Performed   = '2' => "Travel document verification performed",
+    }
+}
+
+code_enum! {
+    /// Item 19, ID/AD Indicator, the kind of industry discount of the ticket.
+    pub enum IdAdIndicator {
+        Idn1PositiveSpace  = '0' => "IDN1 positive space",
+        Idn2SpaceAvailable = '1' => "IDN2 space available",
+        Idb1PositiveSpace  = '2' => "IDB1 positive space",
+        Idb2SpaceAvailable = '3' => "IDB2 space available",
+        Ad                 = '4' => "AD",
+        Dg                 = '5' => "DG",
+        Dm                 = '6' => "DM",
+        Ge                 = '7' => "GE",
+        Ig                 = '8' => "IG",
+        Rg                 = '9' => "RG",
+        Ud                 = 'A' => "UD",
+        IndustryPersonnel  = 'B' => "ID, industry personnel (non-airline)",
+    }
+}
+
+code_enum! {
+    /// Item 254, Fast Track.
+    pub enum FastTrack {
+        Eligible    = 'Y' => "Eligible for fast track",
+        NotEligible = 'N' => "Not eligible for fast track",
+    }
+}
+
+code_enum! {
+    /// Item 28, Type of Security Data, which tells how the security data is to be verified.
+    pub enum SecurityDataType {
+        DigitalSignature = '1' => "Digital signature",
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::str::{self, FromStr};
+
+use super::error::{Error, ErrorKind};
+
+/// A 3-letter IATA location identifier, as in the items 26 and 38.
+///
+/// Letters are kept in the case they are given, `Mode::Strict` is the one rejecting lowercase.
+/// A malformed code read in `Mode::Tolerant` is kept as it is, see [`Self::is_valid()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AirportCode([u8; 3]);
+
+impl AirportCode {
+
+    /// Keeps the 3 characters of a malformed code as they are, so that it is written back
+    /// unchanged. Returns `None` if `s` is not 3 ASCII characters.
+    pub(crate) fn verbatim(s: &str) -> Option<Self> {
+        verbatim(s).map(Self)
+    }
+
+    /// The code, as a string of 3 letters.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII characters are accepted on construction.
+        str::from_utf8(&self.0).unwrap()
+    }
+
+    /// Returns `true` if the code is made of 3 letters, `false` for a malformed code
+    /// read in `Mode::Tolerant`.
+    pub fn is_valid(&self) -> bool {
+        self.as_str().parse::<Self>().is_ok()
+    }
+}
+
+impl fmt::Display for AirportCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AirportCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        if s.len() != 3 {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if !s.bytes().all(|x| x.is_ascii_alphabetic()) {
+            return Err(ErrorKind::AlphaExpected.into())
+        }
+
+        let mut code = [0; 3];
+        code.copy_from_slice(s.as_bytes());
+
+        Ok(Self(code))
+    }
+}
+
+/// An airline designator, either a 2-character IATA designator of letters and digits,
+/// or a 3-letter ICAO designator, as in the items 42, 19, 20 and 21.
+///
+/// Letters are kept in the case they are given, `Mode::Strict` is the one rejecting lowercase.
+/// A malformed designator read in `Mode::Tolerant` is kept as it is, see [`Self::is_valid()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AirlineDesignator([u8; 3]);
+
+impl AirlineDesignator {
+
+    /// Keeps the 3 characters of a malformed designator as they are, so that it is written
+    /// back unchanged. Returns `None` if `s` is not 3 ASCII characters.
+    pub(crate) fn verbatim(s: &str) -> Option<Self> {
+        verbatim(s).map(Self)
+    }
+
+    /// The designator, without the space padding 2-character designators.
+    pub fn as_str(&self) -> &str {
+        let len = self.0.iter().rposition(|&x| x != b' ').map_or(0, |x| x + 1);
+
+        // Only ASCII characters are accepted on construction.
+        str::from_utf8(&self.0[..len]).unwrap()
+    }
+
+    /// Returns `true` if the designator is a 2-character IATA or a 3-letter ICAO designator,
+    /// `false` for a malformed designator read in `Mode::Tolerant`.
+    pub fn is_valid(&self) -> bool {
+        self.as_str().parse::<Self>().is_ok()
+    }
+}
+
+impl fmt::Display for AirlineDesignator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses a designator, which may be padded with a trailing space.
+impl FromStr for AirlineDesignator {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_suffix(' ').unwrap_or(s);
+
+        let valid = match s.len() {
+            2 => s.bytes().all(|x| x.is_ascii_alphanumeric()),
+            3 => s.bytes().all(|x| x.is_ascii_alphabetic()),
+            _ => return Err(ErrorKind::MandatoryDataSize.into()),
+        };
+
+        if !valid {
+            return Err(ErrorKind::AlphaNumExpected.into())
+        }
+
+        let mut code = [b' '; 3];
+        code[..s.len()].copy_from_slice(s.as_bytes());
+
+        Ok(Self(code))
+    }
+}
+
+/// The bytes of `s`, if it is made of 3 ASCII characters.
+fn verbatim(s: &str) -> Option<[u8; 3]> {
+    if s.len() != 3 || !s.is_ascii() {
+        return None
+    }
+
+    let mut code = [0; 3];
+    code.copy_from_slice(s.as_bytes());
+
+    Some(code)
+}
+
+macro_rules! impl_str_traits {
+    ($ty:ident) => {
+        impl PartialEq<str> for $ty {
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl PartialEq<&str> for $ty {
+            fn eq(&self, other: &&str) -> bool {
+                self.as_str() == *other
+            }
+        }
+
+        #[cfg(feature = "with-serde")]
+        impl serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        #[cfg(feature = "with-serde")]
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+impl_str_traits!(AirportCode);
+impl_str_traits!(AirlineDesignator);
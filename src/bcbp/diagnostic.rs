@@ -0,0 +1,29 @@
+use std::ops::Range;
+
+use super::{
+    error::Error,
+    field::Field,
+};
+
+/// How serious a problem reported by [`Bcbp::diagnose()`](super::Bcbp::diagnose) is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// Accepted in `Mode::Tolerant`, rejected in `Mode::Strict`.
+    Warning,
+    /// Rejected in both modes.
+    Error,
+}
+
+/// A problem found in a boarding pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// The item in which the problem was found.
+    pub field: Field,
+    /// The index of the leg the item belongs to, `None` for items of the whole pass.
+    pub leg: Option<usize>,
+    /// The byte range of the item in the input.
+    pub range: Range<usize>,
+    pub severity: Severity,
+    /// The error the parser fails with, depending on the severity and the mode.
+    pub error: Error,
+}
@@ -1,36 +1,184 @@
+use std::fmt;
+
 use super::field::Field;
+use crate::datetime::Error as DateError;
 
-#[derive(Debug, PartialEq)]
-pub enum Error {
+/// What went wrong, see [`Error`] for where.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum ErrorKind {
+    #[error("MANDATORY_DATA_SIZE")]
     MandatoryDataSize,
+
+    #[error("INSUFFICIENT_DATA_LENGTH")]
     InsufficientDataLength,
+
+    #[error("INVALID_FORMAT_CODE: {0:?}")]
     InvalidFormatCode(char),
-    InvalidPrefix(Field, char),
+
+    /// A field expected to begin with a specific character does not.
+    #[error("INVALID_PREFIX: {0:?}")]
+    InvalidPrefix(char),
+
+    #[error("INVALID_LEGS_COUNT")]
     InvalidLegsCount,
+
+    #[error("INVALID_FORMAT")]
     InvalidFormat,
+
+    /// A date or a day of the year is not valid, the source of the error tells why.
+    #[error("INVALID_DATE")]
+    InvalidDate,
+
+    /// A conditional item is not present in the pass, and cannot be set without
+    /// changing the layout of its section.
+    #[error("CONDITIONAL_DATA")]
     CoditionalData,
+
+    /// A section is larger than its size field allows, or smaller than its content.
+    #[error("CONDITIONAL_DATA_SIZE")]
     CoditionalDataSize,
+
     /// The end of the input was reached prematurely.
-    UnexpectedEndOfInput(Field),
+    #[error("UNEXPECTED_END_OF_INPUT")]
+    UnexpectedEndOfInput,
+
     /// The length of the subsection encoded exceeds the remaining length of the input.
+    #[error("SUBSECTION_TOO_LONG")]
     SubsectionTooLong,
+
     /// The contents of a field parsed as a numeric was not a numeric value.
-    ExpectedInteger(Field),
+    #[error("EXPECTED_INTEGER")]
+    ExpectedInteger,
+
     /// The BCBP string does not contain exclusively ASCII characters.
+    #[error("INVALID_CHARACTERS")]
     InvalidCharacters,
+
     /// After parsing, additional characters remain.
+    #[error("TRAILING_DATA")]
     TrailingData,
+
     /// Returned when alpha-numeric characters were expected
+    #[error("ALPHA_NUM_EXPECTED")]
     AlphaNumExpected,
+
     /// Returned when alphabetic characters were expected
+    #[error("ALPHA_EXPECTED")]
     AlphaExpected,
+
     /// Returned when digit charaacters were expected
+    #[error("DIGITS_EXPECTED")]
     DigitsExpected,
+
+    /// A mandatory item is not set, which is not allowed when building in strict mode.
+    #[error("MANDATORY_FIELD_MISSING")]
+    MandatoryFieldMissing,
+}
+
+/// The error type used by the bcbp module.
+///
+/// Errors met while parsing carry the byte offset in the input at which the failing
+/// item starts. The field and the index of the leg are set whenever they apply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    kind: ErrorKind,
+    field: Option<Field>,
+    offset: Option<usize>,
+    leg: Option<usize>,
+    source: Option<DateError>,
+}
+
+impl Error {
+
+    /// What went wrong.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The item which caused the error.
+    pub fn field(&self) -> Option<Field> {
+        self.field
+    }
+
+    /// The byte offset of the item which caused the error, when parsing.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// The index of the leg the item belongs to, `None` for items of the whole pass.
+    pub fn leg(&self) -> Option<usize> {
+        self.leg
+    }
+
+    /// Sets the field, unless already known.
+    pub(crate) fn with_field(mut self, field: Field) -> Self {
+        self.field.get_or_insert(field);
+        self
+    }
+
+    /// Sets the offset, unless already known.
+    pub(crate) fn with_offset(mut self, offset: usize) -> Self {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
+    /// Sets the index of the leg, unless already known.
+    pub(crate) fn with_leg(mut self, leg: Option<usize>) -> Self {
+        if self.leg.is_none() {
+            self.leg = leg;
+        }
+        self
+    }
+
+    /// Replaces what went wrong, keeping the position.
+    pub(crate) fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self { kind, field: None, offset: None, leg: None, source: None }
+    }
+}
+
+impl From<DateError> for Error {
+    fn from(source: DateError) -> Self {
+        Self { source: Some(source), ..ErrorKind::InvalidDate.into() }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if let Some(field) = self.field {
+            write!(f, " in {}", field)?;
+        }
+
+        if let Some(leg) = self.leg {
+            write!(f, " of leg {}", leg)?;
+        }
+
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {}", offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|x| x as _)
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, thiserror::Error)]
 pub enum FixError {
+    #[error("INSUFFICIENT_DATA_LENGTH")]
     InsufficientDataLength,
 }
 
-pub type BcbpResult<T> = std::result::Result<T, Error>;
\ No newline at end of file
+pub type BcbpResult<T> = std::result::Result<T, Error>;
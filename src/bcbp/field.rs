@@ -5,6 +5,48 @@
 
 use std::fmt;
 
+/// Character class of a field as defined in the Implementation Guide.
+#[derive(Copy,Clone,Eq,PartialEq,Debug,Hash)]
+pub enum DataType {
+    /// Data Type 'N'. Numeric characters only.
+    Numeric,
+    /// Data Type 'a'. Upper case alphabetic characters only.
+    Alphabetic,
+    /// Data Type 'f'. Any printable upper case character.
+    Flexible,
+    /// Data Type 'f', holding a hexadecimal length.
+    Hexadecimal,
+    /// Free-form content which is not subject to validation.
+    Unspecified,
+}
+
+impl DataType {
+
+    /// Checks if `value` is valid for the data type.
+    /// A value made of spaces only indicates the field is not set and is always valid,
+    /// except for hexadecimal lengths which are structural.
+    pub fn is_valid(self, value: &str) -> bool {
+        let bytes = value.as_bytes();
+
+        if self != DataType::Hexadecimal && bytes.iter().all(|&c| c == b' ') {
+            return true
+        }
+
+        match self {
+            DataType::Numeric =>
+                bytes.iter().all(u8::is_ascii_digit),
+            DataType::Alphabetic =>
+                bytes.iter().all(u8::is_ascii_uppercase),
+            DataType::Flexible =>
+                bytes.iter().all(|c| (b' '..=b'~').contains(c) && !c.is_ascii_lowercase()),
+            DataType::Hexadecimal =>
+                bytes.iter().all(|c| c.is_ascii_digit() || (b'A'..=b'F').contains(c)),
+            DataType::Unspecified =>
+                true,
+        }
+    }
+}
+
 #[derive(Copy,Clone,Eq,PartialEq,Ord,PartialOrd,Debug,Hash)]
 pub enum Field {
     /// Item 1: Format Code. 1 byte. Data Type 'f'.
@@ -98,6 +140,7 @@ pub enum Field {
 impl Field {
 
     /// The required length of the field. If zero, the field may be arbitrarily long.
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(self) -> usize {
         match self {
             Field::FormatCode => 1,
@@ -146,6 +189,61 @@ impl Field {
         }
     }
 
+    /// Returns `true` if the value of the field is left-justified and padded with spaces.
+    pub fn is_left_justified(self) -> bool {
+        matches!(self,
+            Field::PassengerName |
+            Field::OperatingCarrierPnrCode |
+            Field::OperatingCarrierDesignator |
+            Field::MarketingCarrierDesignator |
+            Field::FrequentFlyerAirlineDesignator |
+            Field::AirlineDesignatorOfBoardingPassIssuer |
+            Field::FrequentFlyerNumber |
+            Field::DocumentFormSerialNumber |
+            Field::FreeBaggageAllowance)
+    }
+
+    /// Checks that a left-justified `value` is followed by spaces only, rather than
+    /// by other fill characters as in `MR.....` or `EJNUF__`. The slash closing
+    /// the surname of a name without given names is part of the value.
+    pub fn is_padding_valid(self, value: &str) -> bool {
+        if !self.is_left_justified() {
+            return true
+        }
+
+        match value.trim_end_matches(' ').chars().last() {
+            Some(c) => c.is_ascii_alphanumeric() || c == '/',
+            None    => true,
+        }
+    }
+
+    /// Character class of the field.
+    /// Security data is carrier-encoded and commonly holds mixed case base64 text,
+    /// so like airline individual use it is left unspecified.
+    pub fn data_type(self) -> DataType {
+        match self {
+            Field::NumberOfLegsEncoded |
+            Field::DateOfIssueOfBoardingPass |
+            Field::DateOfFlight |
+            Field::AirlineNumericCode =>
+                DataType::Numeric,
+            Field::FromCityAirportCode |
+            Field::ToCityAirportCode |
+            Field::CompartmentCode =>
+                DataType::Alphabetic,
+            Field::FieldSizeOfVariableSizeField |
+            Field::FieldSizeOfStructuredMessageUnique |
+            Field::FieldSizeOfStructuredMessageRepeated |
+            Field::LengthOfSecurityData =>
+                DataType::Hexadecimal,
+            Field::AirlineIndividualUse |
+            Field::SecurityData =>
+                DataType::Unspecified,
+            _ =>
+                DataType::Flexible,
+        }
+    }
+
     /// Name of the field as defined in the Implementation Guide.
     pub fn name(self) -> &'static str {
         match self {
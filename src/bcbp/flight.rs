@@ -0,0 +1,170 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::designator::AirlineDesignator;
+use super::error::{Error, ErrorKind, BcbpResult};
+
+/// A flight, identified by the designator of its carrier, its number and an optional
+/// operational suffix, as in `LH400A`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "FlightDesignatorRepr"))]
+pub struct FlightDesignator {
+    carrier: AirlineDesignator,
+    number: u16,
+    suffix: Option<char>,
+}
+
+/// The fields of a [`FlightDesignator`] as they are deserialized, before validation.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+struct FlightDesignatorRepr {
+    carrier: String,
+    number: u16,
+    suffix: Option<char>,
+}
+
+#[cfg(feature = "with-serde")]
+impl TryFrom<FlightDesignatorRepr> for FlightDesignator {
+    type Error = Error;
+
+    fn try_from(repr: FlightDesignatorRepr) -> Result<Self, Self::Error> {
+        Self::new(&repr.carrier, repr.number, repr.suffix)
+    }
+}
+
+impl FlightDesignator {
+
+    /// Constructs a flight designator.
+    ///
+    /// # Errors
+    /// * The errors of [`AirlineDesignator`] parsing `carrier`.
+    /// * [`ErrorKind::MandatoryDataSize`] if `number` is not between 1 and 9999.
+    /// * [`ErrorKind::AlphaExpected`] if `suffix` is not an uppercase letter.
+    pub fn new(carrier: &str, number: u16, suffix: Option<char>) -> BcbpResult<Self> {
+
+        let carrier = carrier.parse()?;
+
+        if !(1..=9999).contains(&number) {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if suffix.is_some_and(|x| !x.is_ascii_uppercase()) {
+            return Err(ErrorKind::AlphaExpected.into())
+        }
+
+        Ok(Self { carrier, number, suffix })
+    }
+
+    /// Parses the items 42 and 43 of a leg, the space padded carrier designator and
+    /// the flight number of 4 digits followed by the suffix or a space, such as `AC 0834 `.
+    pub fn from_bcbp(src: &str) -> BcbpResult<Self> {
+
+        if !src.is_ascii() {
+            return Err(ErrorKind::InvalidCharacters.into())
+        }
+
+        if src.len() != 8 {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        let (carrier, flight) = src.split_at(3);
+        let (number, suffix) = number_from_str(flight.trim())?;
+
+        Self::new(carrier.trim_end(), number, suffix)
+    }
+
+    /// Encodes the items 42 and 43 of a leg, see [`FlightDesignator::from_bcbp()`].
+    pub fn to_bcbp(&self) -> String {
+        format!("{:<3}{:04}{}", self.carrier.as_str(), self.number, self.suffix.unwrap_or(' '))
+    }
+
+    /// The 2-character IATA or 3-letter ICAO designator of the carrier.
+    pub fn carrier(&self) -> AirlineDesignator {
+        self.carrier
+    }
+
+    /// The number of the flight.
+    pub fn number(&self) -> u16 {
+        self.number
+    }
+
+    /// The operational suffix of the flight.
+    pub fn suffix(&self) -> Option<char> {
+        self.suffix
+    }
+}
+
+/// Writes the common form `LH400A`, or `LH 0400A` with the alternate flag.
+///
+/// ```
+/// use iata::bcbp::FlightDesignator;
+///
+/// let flight = FlightDesignator::new("LH", 400, Some('A')).unwrap();
+///
+/// assert_eq!(format!("{}", flight),   "LH400A");
+/// assert_eq!(format!("{:#}", flight), "LH 0400A");
+/// assert_eq!(flight.to_bcbp(),        "LH 0400A");
+/// ```
+impl fmt::Display for FlightDesignator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{} {:04}", self.carrier, self.number)?;
+        } else {
+            write!(f, "{}{}", self.carrier, self.number)?;
+        }
+
+        if let Some(suffix) = self.suffix {
+            write!(f, "{}", suffix)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses any of the forms `LH400A`, `LH 0400A` or `EZY1234`, including the
+/// BCBP fixed-width form.
+impl FromStr for FlightDesignator {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if !s.is_ascii() {
+            return Err(ErrorKind::InvalidCharacters.into())
+        }
+
+        // A 3-letter designator is alphabetic, the third character of a flight
+        // with a 2-character designator is the first digit of the number.
+        let carrier_len = match s.find(' ') {
+            Some(idx) => idx,
+            None if s.bytes().take(3).all(|x| x.is_ascii_alphabetic()) => 3,
+            None => 2,
+        };
+
+        if s.len() <= carrier_len {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        let (carrier, flight) = s.split_at(carrier_len);
+        let (number, suffix) = number_from_str(flight.trim_start())?;
+
+        Self::new(carrier, number, suffix)
+    }
+}
+
+/// Splits up to 4 digits from the suffix which may follow them.
+fn number_from_str(src: &str) -> BcbpResult<(u16, Option<char>)> {
+
+    let (number, suffix) = match src.bytes().last() {
+        Some(x) if x.is_ascii_alphabetic() => (&src[..src.len() - 1], Some(x as char)),
+        _ => (src, None),
+    };
+
+    if number.is_empty() || number.len() > 4 || !number.bytes().all(|x| x.is_ascii_digit()) {
+        return Err(ErrorKind::DigitsExpected.into())
+    }
+
+    // Up to four digits, which always fit.
+    Ok((number.parse().unwrap(), suffix))
+}
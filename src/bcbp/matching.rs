@@ -0,0 +1,300 @@
+//! Comparison of the name of a pass with the name of a person, as read from
+//! a passenger manifest or a travel document.
+
+use super::name::PassengerName;
+use super::translit::transliterate;
+
+/// How likely a name belongs to the same person, from the least to the most likely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub enum MatchConfidence {
+    /// The surnames differ.
+    None,
+    /// The surnames agree, the given names differ or are missing, or the names
+    /// only agree as spelling variants.
+    Low,
+    /// The names agree once the differences listed as reasons are allowed for.
+    High,
+    /// The names are the same, letter for letter.
+    Exact,
+}
+
+/// A difference allowed for, or found, when comparing two names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub enum MatchReason {
+    /// The name of the pass is the beginning of the other name, cut at 20 characters.
+    Truncated,
+    /// A title is present in one name only, or the titles differ.
+    TitleIgnored,
+    /// The names differ in their spaces and hyphens.
+    SeparatorsIgnored,
+    /// The other name is written with letters outside of the character set of the pass.
+    Transliterated,
+    /// The names differ in a way transliteration systems do, such as `MULLER` for `MUELLER`.
+    /// Only allowed for where a name is written with letters which needed transliterating.
+    TransliterationVariant,
+    /// The names differ in a way transliteration systems do, such as `BRUNER` for `BRUNNER`,
+    /// while neither is written with letters which needed transliterating.
+    SpellingVariant,
+    /// Given names of the pass are the initials of the other ones.
+    Initials,
+    /// Given names of the other name are absent from the pass.
+    MiddleNamesMissing,
+    /// The pass has no given names.
+    GivenNamesMissing,
+    /// The given names differ.
+    GivenNamesMismatch,
+    /// The surnames differ.
+    SurnameMismatch,
+}
+
+/// The outcome of comparing two names, see [`PassengerName::match_name()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub struct NameMatch {
+    confidence: MatchConfidence,
+    reasons: Vec<MatchReason>,
+}
+
+impl NameMatch {
+
+    /// How likely the names belong to the same person.
+    pub fn confidence(&self) -> MatchConfidence {
+        self.confidence
+    }
+
+    /// The differences between the names, each listed once.
+    pub fn reasons(&self) -> &[MatchReason] {
+        &self.reasons
+    }
+
+    /// Returns `true` if the confidence is at least [`MatchConfidence::High`].
+    pub fn is_match(&self) -> bool {
+        self.confidence >= MatchConfidence::High
+    }
+
+    fn add(&mut self, reason: MatchReason) {
+        if !self.reasons.contains(&reason) {
+            self.reasons.push(reason);
+        }
+    }
+}
+
+impl PassengerName {
+
+    /// Compares the receiver, the name of a pass, with the name of a person.
+    ///
+    /// ```
+    /// use iata::bcbp::{PassengerName, MatchConfidence, MatchReason};
+    ///
+    /// let pass = PassengerName::from_bcbp("MUELLER LUEDENSCHEID");
+    /// let person = PassengerName::new("Müller-Lüdenscheidt", Some("José"), None);
+    /// let result = pass.match_name(&person);
+    ///
+    /// assert_eq!(result.confidence(), MatchConfidence::High);
+    /// assert!(result.reasons().contains(&MatchReason::Truncated));
+    /// assert!(result.reasons().contains(&MatchReason::Transliterated));
+    /// ```
+    pub fn match_name(&self, other: &PassengerName) -> NameMatch {
+        let mut result = NameMatch { confidence: MatchConfidence::Exact, reasons: Vec::new() };
+
+        let other_surname = other.surname();
+        let other_given = other.given_names().unwrap_or("");
+
+        if !other_surname.is_ascii() || !other_given.is_ascii() {
+            result.add(MatchReason::Transliterated);
+        }
+
+        if self.title() != other.title() {
+            result.add(MatchReason::TitleIgnored);
+        }
+
+        let surname = words(self.surname());
+        let variant = variant_reason(self.surname(), other_surname);
+        let other_surname = words(other_surname);
+
+        // The surname comes first, so a cut pass may have no given names at all.
+        let surname_cut = self.given_names().is_none() && self.title().is_none();
+        let truncated = self.is_possibly_truncated() && surname_cut;
+
+        if !match_words(&surname, &other_surname, truncated, variant, &mut result) {
+            result.add(MatchReason::SurnameMismatch);
+            result.confidence = MatchConfidence::None;
+            return result
+        }
+
+        let variant = variant_reason(self.given_names().unwrap_or(""), other_given);
+        let given = words(self.given_names().unwrap_or(""));
+        let other_given = words(other_given);
+
+        if given.is_empty() {
+            // Given names cut off along with the end of the surname are not missing.
+            let cut_off = surname_cut && result.reasons.contains(&MatchReason::Truncated);
+
+            if !(other_given.is_empty() || cut_off) {
+                result.add(MatchReason::GivenNamesMissing);
+            }
+        } else if !match_given_names(&given, &other_given, self.is_possibly_truncated(), variant, &mut result) {
+            result.add(MatchReason::GivenNamesMismatch);
+        }
+
+        result.confidence = if result.reasons.iter().any(|x| matches!(x,
+            MatchReason::GivenNamesMissing | MatchReason::GivenNamesMismatch | MatchReason::SpellingVariant))
+        {
+            MatchConfidence::Low
+        } else if result.reasons.is_empty() {
+            MatchConfidence::Exact
+        } else {
+            MatchConfidence::High
+        };
+
+        result
+    }
+}
+
+/// Splits a name into transliterated words.
+fn words(src: &str) -> Vec<String> {
+    transliterate(src).split(' ').filter(|x| !x.is_empty()).map(str::to_owned).collect()
+}
+
+/// Returns the reason to give for names which only agree by their skeletons:
+/// a transliteration variant if either name needed transliterating, a mere
+/// spelling variant otherwise.
+fn variant_reason(name: &str, other: &str) -> MatchReason {
+    if name.is_ascii() && other.is_ascii() {
+        MatchReason::SpellingVariant
+    } else {
+        MatchReason::TransliterationVariant
+    }
+}
+
+/// Compares names made of one or more words, such as compound surnames.
+/// Names alike only by their skeletons are reported with the `variant` reason.
+fn match_words(words: &[String], other: &[String], truncated: bool, variant: MatchReason, result: &mut NameMatch) -> bool {
+    if words == other {
+        return true
+    }
+
+    let joined = words.concat();
+    let other_joined = other.concat();
+
+    if truncated && joined.len() < other_joined.len() {
+        let spaced = words.join(" ");
+
+        if other.join(" ").starts_with(&spaced) {
+            result.add(MatchReason::Truncated);
+            return true
+        }
+
+        if other_joined.starts_with(&joined) {
+            result.add(MatchReason::Truncated);
+            result.add(MatchReason::SeparatorsIgnored);
+            return true
+        }
+    }
+
+    if joined == other_joined {
+        result.add(MatchReason::SeparatorsIgnored);
+        return true
+    }
+
+    let joined = skeleton(&joined);
+    let other_joined = skeleton(&other_joined);
+
+    if joined == other_joined || (truncated && other_joined.starts_with(&joined)) {
+        result.add(variant);
+        if joined != other_joined {
+            result.add(MatchReason::Truncated);
+        }
+        return true
+    }
+
+    false
+}
+
+/// Compares given names in order, accepting initials, missing middle names and,
+/// if `truncated`, a cut last word. Names alike only by their skeletons are reported
+/// with the `variant` reason.
+fn match_given_names(given: &[String], other: &[String], truncated: bool, variant: MatchReason, result: &mut NameMatch) -> bool {
+    if given == other {
+        return true
+    }
+
+    if given.concat() == other.concat() {
+        result.add(MatchReason::SeparatorsIgnored);
+        return true
+    }
+
+    let mut others = other.iter();
+
+    for (idx, word) in given.iter().enumerate() {
+        let last = idx + 1 == given.len();
+
+        // Given names of the pass may skip middle names of the other name.
+        let found = others.by_ref().enumerate().find_map(|(skipped, other)| {
+            if word == other {
+                Some((skipped, None))
+            } else if word.len() == 1 && other.starts_with(word.as_str()) {
+                Some((skipped, Some(MatchReason::Initials)))
+            } else if last && truncated && other.starts_with(word.as_str()) {
+                Some((skipped, Some(MatchReason::Truncated)))
+            } else if skeleton(word) == skeleton(other) {
+                Some((skipped, Some(variant)))
+            } else {
+                None
+            }
+        });
+
+        match found {
+            Some((skipped, reason)) => {
+                if skipped > 0 {
+                    result.add(MatchReason::MiddleNamesMissing);
+                }
+                if let Some(reason) = reason {
+                    result.add(reason);
+                }
+            },
+            None => return false,
+        }
+    }
+
+    if others.next().is_some() {
+        result.add(MatchReason::MiddleNamesMissing);
+    }
+
+    true
+}
+
+/// Reduces a transliterated word to the letters most spellings agree on, so that
+/// `MUELLER`, `MULLER` and `MULER` or `IULIIA` and `YULIYA` are alike.
+fn skeleton(src: &str) -> String {
+    const DIGRAPHS: &[(&str, &str)] = &[
+        ("AE", "A"), ("OE", "O"), ("UE", "U"), ("AA", "A"),
+        ("KH", "H"), ("PH", "F"), ("TH", "T"), ("CK", "K"),
+    ];
+
+    let mut ret = src.to_owned();
+
+    for (from, to) in DIGRAPHS {
+        ret = ret.replace(from, to);
+    }
+
+    let mut skeleton = String::with_capacity(ret.len());
+
+    for c in ret.chars() {
+        let c = match c {
+            'Y' | 'J' => 'I',
+            'W'       => 'V',
+            'Z'       => 'S',
+            c         => c,
+        };
+
+        // Doubled letters are written once.
+        if !skeleton.ends_with(c) {
+            skeleton.push(c);
+        }
+    }
+
+    skeleton
+}
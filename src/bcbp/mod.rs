@@ -1,11 +1,29 @@
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::str;
-use std::u32;
 
 use time::Date;
 
 mod error;
+mod bagtag;
+mod seat;
+mod flight;
+mod designator;
+mod ticket;
+mod sequence;
+mod name;
+mod translit;
+mod matching;
+mod airline_use;
+mod version;
+mod diagnostic;
+mod codes;
 pub mod field;
 pub mod raw;
+#[cfg(feature = "with-serde")]
+pub mod serde_as_string;
+#[cfg(feature = "with-security")]
+pub mod security;
 pub(crate) mod chunk;
 
 use chunk::Chunk;
@@ -13,25 +31,96 @@ use field::Field;
 
 pub use crate::bcbp::error::{
     Error,
+    ErrorKind,
     FixError,
     BcbpResult,
 };
 
-use crate::datetime::{DayOfYear, Error as DateError};
+pub use crate::bcbp::bagtag::{
+    BagTagKind,
+    BagTagRange,
+};
+
+pub use crate::bcbp::seat::Seat;
+
+pub use crate::bcbp::flight::FlightDesignator;
+
+pub use crate::bcbp::designator::{
+    AirportCode,
+    AirlineDesignator,
+};
+
+pub use crate::bcbp::ticket::TicketNumber;
+
+pub use crate::bcbp::sequence::CheckInSequence;
+
+pub use crate::bcbp::name::{
+    PassengerName,
+    Title,
+};
+
+pub use crate::bcbp::translit::transliterate;
+
+pub use crate::bcbp::matching::{
+    NameMatch,
+    MatchConfidence,
+    MatchReason,
+};
+
+pub use crate::bcbp::airline_use::{
+    AirlineUseDecoder,
+    AirlineUseRegistry,
+    AirlineData,
+    AirlineValue,
+    AlaskaDecoder,
+    StarAllianceDecoder,
+};
+
+pub use crate::bcbp::version::BcbpVersion;
+
+pub use crate::bcbp::codes::{
+    TicketIndicator,
+    CheckInSource,
+    BoardingPassSource,
+    DocumentType,
+    SelecteeIndicator,
+    DocumentVerification,
+    IdAdIndicator,
+    FastTrack,
+    SecurityDataType,
+};
 
+pub use crate::bcbp::diagnostic::{
+    Diagnostic,
+    Severity,
+};
+
+use crate::datetime::{DayOfYear, JulianIssueDate, Error as DateError};
+
+
+/// Version number written when a pass carrying conditional data does not specify one.
+const DEFAULT_VERSION: BcbpVersion = BcbpVersion::V6;
 
-#[derive(Debug, PartialEq)]
+/// Controls how closely parsing and building follow Resolution 792.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Mode {
+    /// Accepts the quirks seen in real-world passes, such as mixed case names
+    /// or unused bytes at the end of a structured message.
+    #[default]
     Tolerant,
+    /// Rejects lowercase input, characters outside of the data type of a field,
+    /// padding other than spaces and section sizes which do not match their content.
+    /// Building requires every mandatory field and the version to be set.
     Strict
 }
 
 
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PaxStatus {
     None,
+    #[default]
     NotCheckedIn,
     CheckedIn,
     Other(char),
@@ -58,25 +147,35 @@ impl PaxStatus {
         }
 
     }
-}
 
-impl Default for PaxStatus {
-    fn default() -> Self { PaxStatus::NotCheckedIn }
+    /// Meaning of the value, as given by Resolution 792.
+    pub fn description(&self) -> &'static str {
+        use PaxStatus::*;
+        match *self {
+            None          => "Not set",
+            NotCheckedIn  => "Ticket issuance, passenger not checked in",
+            CheckedIn     => "Ticket issuance, passenger checked in",
+            Other(_)      => "Unknown"
+        }
+    }
 }
 
-
-
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+/// Item 15, Passenger Description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PaxType {
-    None,            // ' '
-    Adult,           // 0
-    Male,            // 1
-    Female,          // 2
-    Child,           // 3
-    Infant,          // 4
-    CabinBaggage,    // 5
-    AdultWithInfant, // 6
+    #[default]
+    None,               // ' '
+    Adult,              // 0
+    Male,               // 1
+    Female,             // 2
+    Child,              // 3
+    Infant,             // 4
+    CabinBaggage,       // 5
+    AdultWithInfant,    // 6
+    UnaccompaniedMinor, // 7
+    Undisclosed,        // 8
+    Unspecified,        // 9
     Other(char),
 }
 
@@ -90,40 +189,80 @@ impl PaxType {
             '2' => Female,
             '3' => Child,
             '4' => Infant,
-            '6' => CabinBaggage,
-            '7' => AdultWithInfant,
+            '5' => CabinBaggage,
+            '6' => AdultWithInfant,
+            '7' => UnaccompaniedMinor,
+            '8' => Undisclosed,
+            '9' => Unspecified,
             _   => Other(t)
         }
     }
-}
 
-impl Default for PaxType {
-    fn default() -> Self { PaxType::None }
+    pub fn to_char(&self) -> char {
+        use PaxType::*;
+        match *self {
+            None               => ' ',
+            Adult              => '0',
+            Male               => '1',
+            Female             => '2',
+            Child              => '3',
+            Infant             => '4',
+            CabinBaggage       => '5',
+            AdultWithInfant    => '6',
+            UnaccompaniedMinor => '7',
+            Undisclosed        => '8',
+            Unspecified        => '9',
+            Other(t)           => t
+        }
+    }
+
+    /// Meaning of the value, as given by Resolution 792.
+    pub fn description(&self) -> &'static str {
+        use PaxType::*;
+        match *self {
+            None               => "Not set",
+            Adult              => "Adult",
+            Male               => "Male",
+            Female             => "Female",
+            Child              => "Child",
+            Infant             => "Infant",
+            CabinBaggage       => "No passenger (cabin baggage)",
+            AdultWithInfant    => "Adult traveling with infant",
+            UnaccompaniedMinor => "Unaccompanied minor",
+            Undisclosed        => "Undisclosed gender",
+            Unspecified        => "Unspecified gender",
+            Other(_)           => "Unknown"
+        }
+    }
 }
 
-#[derive(Debug, Default, Clone)]
-#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "LegRepr"))]
 pub struct Leg {
     pnr: Option<String>,
-    src_airport: Option<String>,
-    dst_airport: Option<String>,
-    airline: Option<String>,
+    src_airport: Option<AirportCode>,
+    dst_airport: Option<AirportCode>,
+    airline: Option<AirlineDesignator>,
     flight_number: Option<String>,
     pub flight_day:    Option<DayOfYear>,
     pub compartment: Option<char>,
-    seat: Option<String>,
+    seat: Option<Seat>,
     pub airline_num: Option<u16>,
-    pub sequence: Option<u16>,
+    pub sequence: Option<CheckInSequence>,
     pub pax_status: PaxStatus,
     doc_number: Option<String>,
-    // Selectee
+    selectee: SelecteeIndicator,
+    doc_verification: DocumentVerification,
     // marketing_airline
-    marketing_airline: Option<String>,
-    frequent_flyer_airline: Option<String>,
+    marketing_airline: Option<AirlineDesignator>,
+    frequent_flyer_airline: Option<AirlineDesignator>,
     frequent_flyer_number: Option<String>,
-    pub fast_track: Option<char>,
-    // ID/AD indicator
+    pub fast_track: FastTrack,
+    id_ad: IdAdIndicator,
     bag_allowance: Option<String>,
+    /// Data following the repeated fields defined by the version of the pass.
+    pub unexpected_data: Option<String>,
     // data
     pub var: Option<String>,
 }
@@ -143,13 +282,63 @@ macro_rules! gen_get_set {
             get_set $method_name($preprocess) for $field_name
             with |s: &str| {
                 if !($from..=$to).contains(&s.len()) {
-                    Err(Error::MandatoryDataSize)
+                    Err(Error::from(ErrorKind::MandatoryDataSize))
                 } else {
                     Ok(())
                 }
             }
         );
     };
+    (get_set $method_name:ident for $field_name:ident as code $ty:ident) => {
+        pub fn $method_name(&mut self, code: $ty) -> BcbpResult<()> {
+            if let $ty::Other(c) = code {
+                if !c.is_ascii_graphic() {
+                    return Err(ErrorKind::InvalidCharacters.into());
+                }
+            }
+
+            self.$field_name = code;
+            Ok(())
+        }
+
+        pub fn $field_name(&self) -> $ty {
+            self.$field_name
+        }
+    };
+    (get_set $method_name:ident for $field_name:ident as copy $ty:ty) => {
+        pub fn $method_name(&mut self, s: &str) -> BcbpResult<()> {
+            let s = s.trim();
+
+            self.$field_name = if s.is_empty() {
+                None
+            } else {
+                Some(s.parse::<$ty>()?)
+            };
+
+            Ok(())
+        }
+
+        pub fn $field_name(&self) -> Option<$ty> {
+            self.$field_name
+        }
+    };
+    (get_set $method_name:ident for $field_name:ident as $ty:ty) => {
+        pub fn $method_name(&mut self, s: &str) -> BcbpResult<()> {
+            let s = s.trim();
+
+            self.$field_name = if s.is_empty() {
+                None
+            } else {
+                Some(s.parse::<$ty>()?)
+            };
+
+            Ok(())
+        }
+
+        pub fn $field_name(&self) -> Option<&$ty> {
+            self.$field_name.as_ref()
+        }
+    };
     (get_set $method_name:ident($preprocess:path) for $field_name:ident with $verify:expr) => {
         pub fn $method_name(&mut self, s: &str) -> BcbpResult<()> {
             let s = $preprocess(s);
@@ -183,67 +372,256 @@ impl Leg {
         Ok(())
     }
 
+    /// The flight designator of the operating carrier, if the carrier and the
+    /// flight number are set and valid.
+    pub fn flight_designator(&self) -> Option<FlightDesignator> {
+        let src = format!("{:<3}{:<5}", self.airline?.as_str(), self.flight_number.as_deref()?);
+        FlightDesignator::from_bcbp(&src).ok()
+    }
+
+    /// Sets the operating carrier and the flight number.
+    pub fn set_flight_designator(&mut self, flight: &FlightDesignator) {
+        let number = flight.to_bcbp();
+        self.airline = Some(flight.carrier());
+        self.flight_number = Some(number[3..].trim_end().to_owned());
+    }
+
+    /// The electronic ticket number, if the airline numeric code and the
+    /// document serial number are both set.
+    pub fn ticket_number(&self) -> Option<TicketNumber> {
+        let airline = self.airline_num?;
+        let serial = self.doc_number.as_deref()?;
+
+        TicketNumber::from_parts(&format!("{:03}", airline), serial).ok()
+    }
+
+    /// Sets the airline numeric code and the document serial number.
+    pub fn set_ticket_number(&mut self, ticket: &TicketNumber) {
+        self.airline_num = Some(ticket.airline());
+        self.doc_number = Some(format!("{:010}", ticket.serial()));
+    }
+
     gen_get_set!(get_set set_pnr for pnr with len 7);
-    gen_get_set!(get_set set_src_airport for src_airport with len 3);
-    gen_get_set!(get_set set_dst_airport for dst_airport with len 3);
-    gen_get_set!(get_set set_airline for airline with len 3);
+    gen_get_set!(get_set set_src_airport for src_airport as copy AirportCode);
+    gen_get_set!(get_set set_dst_airport for dst_airport as copy AirportCode);
+    gen_get_set!(get_set set_airline for airline as copy AirlineDesignator);
     gen_get_set!(get_set set_flight_number for flight_number with len 5);
-    gen_get_set!(get_set set_seat(Leg::seat_preprocess) for seat with len 4);
+    gen_get_set!(get_set set_seat for seat as Seat);
     gen_get_set!(get_set set_doc_number for doc_number with len 10);
-    gen_get_set!(get_set set_marketing_airline for marketing_airline with len 3);
-    gen_get_set!(get_set set_frequent_flyer_airline for frequent_flyer_airline with len 3);
+    gen_get_set!(get_set set_marketing_airline for marketing_airline as copy AirlineDesignator);
+    gen_get_set!(get_set set_frequent_flyer_airline for frequent_flyer_airline as copy AirlineDesignator);
     gen_get_set!(get_set set_frequent_flyer_numbder for frequent_flyer_number with len 16);
     gen_get_set!(get_set set_bag_allowance for bag_allowance with len 3);
+    gen_get_set!(get_set set_selectee for selectee as code SelecteeIndicator);
+    gen_get_set!(get_set set_doc_verification for doc_verification as code DocumentVerification);
+    gen_get_set!(get_set set_id_ad for id_ad as code IdAdIndicator);
+
+    /// Returns the first mandatory item of the leg which is not set.
+    fn missing_mandatory(&self) -> Option<Field> {
+        if self.pnr.is_none() {
+            Some(Field::OperatingCarrierPnrCode)
+        } else if self.src_airport.is_none() {
+            Some(Field::FromCityAirportCode)
+        } else if self.dst_airport.is_none() {
+            Some(Field::ToCityAirportCode)
+        } else if self.airline.is_none() {
+            Some(Field::OperatingCarrierDesignator)
+        } else if self.flight_number.is_none() {
+            Some(Field::FlightNumber)
+        } else if self.flight_day.is_none() {
+            Some(Field::DateOfFlight)
+        } else if self.compartment.is_none() {
+            Some(Field::CompartmentCode)
+        } else if self.seat.is_none() {
+            Some(Field::SeatNumber)
+        } else if self.sequence.is_none() {
+            Some(Field::CheckInSequenceNumber)
+        } else if self.pax_status == PaxStatus::None {
+            Some(Field::PassengerStatus)
+        } else {
+            None
+        }
+    }
+}
 
-    fn seat_preprocess(s: &str) -> &str { s.trim().trim_start_matches('0') }
+/// The values of the items of the leg, as they are deserialized. The items whose
+/// setters check more than their type are set through them.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+struct LegRepr {
+    pnr: Option<String>,
+    src_airport: Option<AirportCode>,
+    dst_airport: Option<AirportCode>,
+    airline: Option<AirlineDesignator>,
+    flight_number: Option<String>,
+    flight_day: Option<DayOfYear>,
+    compartment: Option<char>,
+    seat: Option<Seat>,
+    airline_num: Option<u16>,
+    sequence: Option<CheckInSequence>,
+    pax_status: PaxStatus,
+    doc_number: Option<String>,
+    selectee: SelecteeIndicator,
+    doc_verification: DocumentVerification,
+    marketing_airline: Option<AirlineDesignator>,
+    frequent_flyer_airline: Option<AirlineDesignator>,
+    frequent_flyer_number: Option<String>,
+    fast_track: FastTrack,
+    id_ad: IdAdIndicator,
+    bag_allowance: Option<String>,
+    unexpected_data: Option<String>,
+    var: Option<String>,
 }
 
-#[derive(Debug, Default, Clone)]
-#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+#[cfg(feature = "with-serde")]
+impl TryFrom<LegRepr> for Leg {
+    type Error = Error;
+
+    fn try_from(repr: LegRepr) -> BcbpResult<Self> {
+        let mut leg = Leg {
+            src_airport:            repr.src_airport,
+            dst_airport:            repr.dst_airport,
+            airline:                repr.airline,
+            flight_day:             repr.flight_day,
+            compartment:            repr.compartment,
+            seat:                   repr.seat,
+            airline_num:            repr.airline_num,
+            sequence:               repr.sequence,
+            pax_status:             repr.pax_status,
+            marketing_airline:      repr.marketing_airline,
+            frequent_flyer_airline: repr.frequent_flyer_airline,
+            fast_track:             repr.fast_track,
+            unexpected_data:        repr.unexpected_data,
+            var:                    repr.var,
+            ..Leg::default()
+        };
+
+        leg.set_pnr(repr.pnr.as_deref().unwrap_or_default())
+            .map_err(|e| e.with_field(Field::OperatingCarrierPnrCode))?;
+        leg.set_flight_number(repr.flight_number.as_deref().unwrap_or_default())
+            .map_err(|e| e.with_field(Field::FlightNumber))?;
+        leg.set_doc_number(repr.doc_number.as_deref().unwrap_or_default())
+            .map_err(|e| e.with_field(Field::DocumentFormSerialNumber))?;
+        leg.set_frequent_flyer_numbder(repr.frequent_flyer_number.as_deref().unwrap_or_default())
+            .map_err(|e| e.with_field(Field::FrequentFlyerNumber))?;
+        leg.set_bag_allowance(repr.bag_allowance.as_deref().unwrap_or_default())
+            .map_err(|e| e.with_field(Field::FreeBaggageAllowance))?;
+        leg.set_selectee(repr.selectee)
+            .map_err(|e| e.with_field(Field::SelecteeIndicator))?;
+        leg.set_doc_verification(repr.doc_verification)
+            .map_err(|e| e.with_field(Field::InternationalDocumentVerification))?;
+        leg.set_id_ad(repr.id_ad)
+            .map_err(|e| e.with_field(Field::IdAdIndicator))?;
+
+        Ok(leg)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bcbp {
-    pub version: Option<char>,
+    pub version: Option<BcbpVersion>,
     pub pax_type: PaxType,
-    pub doc_type: Option<char>,
+    pub doc_type: DocumentType,
     pub name_last: String,
     pub name_first: Option<String>,
-    pub ticket_flag: Option<char>,
+    pub ticket_flag: TicketIndicator,
     pub legs: Vec<Leg>,
-    bagtag1: Option<String>,
-    bagtag2: Option<String>,
-    bagtag3: Option<String>,
-    pub checkin_src: Option<char>,
-    pub boardingpass_src: Option<char>,
-    pub boardingpass_issued: Option<u16>,
-    boardingpass_airline: Option<String>,
-    pub security_data_type: Option<char>,
+    bagtag1: Option<BagTagRange>,
+    bagtag2: Option<BagTagRange>,
+    bagtag3: Option<BagTagRange>,
+    pub checkin_src: CheckInSource,
+    pub boardingpass_src: BoardingPassSource,
+    pub boardingpass_issued: Option<JulianIssueDate>,
+    boardingpass_airline: Option<AirlineDesignator>,
+    pub security_data_type: SecurityDataType,
     pub security_data: Option<String>,
+    /// Data following the unique fields defined by the version of the pass.
+    pub unexpected_data: Option<String>,
 }
 
 impl Bcbp {
-    fn verify_bagtag(s: &str) -> BcbpResult<()> {
-        if s.len() != 13 {
-            Err(Error::MandatoryDataSize)
-        } else if !s.as_bytes().iter().all(u8::is_ascii_digit) {
-            Err(Error::DigitsExpected)
-        } else {
-            Ok(())
-        }
-    }
-
-    gen_get_set!(get_set set_bagtag1(str::trim) for bagtag1 with Bcbp::verify_bagtag);
-    gen_get_set!(get_set set_bagtag2(str::trim) for bagtag2 with Bcbp::verify_bagtag);
-    gen_get_set!(get_set set_bagtag3(str::trim) for bagtag3 with Bcbp::verify_bagtag);
-    gen_get_set!(get_set set_boradingpass_airline for boardingpass_airline with len 3);
-
+    gen_get_set!(get_set set_bagtag1 for bagtag1 as BagTagRange);
+    gen_get_set!(get_set set_bagtag2 for bagtag2 as BagTagRange);
+    gen_get_set!(get_set set_bagtag3 for bagtag3 as BagTagRange);
+    gen_get_set!(get_set set_boradingpass_airline for boardingpass_airline as copy AirlineDesignator);
+
+    /// The name of the passenger as written in the pass. A name with characters other
+    /// than uppercase letters, digits, spaces and the slash, or longer than 20 characters,
+    /// is converted with [`PassengerName::to_bcbp()`].
     pub fn name(&self) -> String {
-        let mut tmp = if let Some(ref name_first) = self.name_first {
+        let name = if let Some(ref name_first) = self.name_first {
             format!("{}/{}", self.name_last, name_first)
         } else {
             self.name_last.clone()
         };
 
-        tmp.truncate(20);
-        tmp
+        // A name read from a pass is kept as it is, to be built back unchanged.
+        let written = name.bytes().all(|x| matches!(x, b'A'..=b'Z' | b'0'..=b'9' | b' ' | b'/'));
+
+        if written && name.len() <= Field::PassengerName.len() {
+            name
+        } else {
+            PassengerName::from_bcbp(&name).to_bcbp()
+        }
+    }
+
+    /// The name of the passenger split into the surname, the given names and the title.
+    pub fn passenger_name(&self) -> PassengerName {
+        PassengerName::from_bcbp(&self.name())
+    }
+
+    /// Compares the name of the passenger with `full_name`, written either as
+    /// `SURNAME/GIVEN NAMES` or as `Given Names Surname`.
+    ///
+    /// ```
+    /// use iata::bcbp::{Bcbp, MatchConfidence};
+    ///
+    /// let pass = Bcbp::from("M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100").unwrap();
+    ///
+    /// assert_eq!(pass.match_name("Roman Bruner").confidence(),       MatchConfidence::High);
+    /// assert_eq!(pass.match_name("BRUNER/ROMAN MR").confidence(),    MatchConfidence::Exact);
+    /// assert_eq!(pass.match_name("Roman Brunner").confidence(),      MatchConfidence::Low);
+    /// assert_eq!(pass.match_name("Roman Braun").confidence(),        MatchConfidence::None);
+    /// ```
+    pub fn match_name(&self, full_name: &str) -> NameMatch {
+        let pass = self.passenger_name();
+        let full_name = full_name.to_uppercase();
+
+        if full_name.contains('/') {
+            return pass.match_name(&PassengerName::from_bcbp(&full_name))
+        }
+
+        // Any number of the last words may be the surname.
+        let words: Vec<&str> = full_name.split_whitespace().collect();
+
+        (0..words.len())
+            .map(|idx| {
+                let given = words[..idx].join(" ");
+                pass.match_name(&PassengerName::new(&words[idx..].join(" "), Some(&given), None))
+            })
+            .max_by(|a, b| {
+                a.confidence().cmp(&b.confidence()).then(b.reasons().len().cmp(&a.reasons().len()))
+            })
+            .unwrap_or_else(|| pass.match_name(&PassengerName::default()))
+    }
+
+    /// Sets the name of the passenger, which is cut to 20 characters when building.
+    pub fn set_passenger_name(&mut self, name: &PassengerName) {
+        let full = name.to_string();
+        let (last, first) = bcbp_name(&full);
+
+        self.name_last  = last;
+        self.name_first = first;
+    }
+
+    /// Resolves the date of issue of the boarding pass, which is not allowed to be
+    /// more than a day later than `for_date`. See [`JulianIssueDate::to_date_adapt()`].
+    pub fn issue_date(&self, for_date: Date) -> Result<Option<Date>, DateError> {
+        self.boardingpass_issued
+            .as_ref()
+            .map(|x| x.to_date_adapt(for_date))
+            .transpose()
     }
 
     pub fn legs_count(&self) -> u8 {
@@ -262,40 +640,51 @@ impl Bcbp {
         &mut self.legs
     }
 
-    pub fn build(&self, _mode: Mode) -> BcbpResult<String> {
+    pub fn build(&self, mode: Mode) -> BcbpResult<String> {
 
+        if mode == Mode::Strict {
+            self.verify_mandatory()?;
+        }
 
-        let mut ret = format!("M{}{:<20}{}", self.legs_count(), self.name(), self.ticket_flag.unwrap_or(' '));
+        let mut ret = format!("M{}{:<20}{}", self.legs_count(), self.name(), self.ticket_flag.to_char());
 
-        for leg in &self.legs {
+        for (leg_index, leg) in self.legs.iter().enumerate() {
 
-            let seat = if let Some(ref seat) = leg.seat {
-                let is_normal_seat =
-                    seat.as_bytes().first()
-                    .unwrap() // The code excludes containing empty string
-                    .is_ascii_digit();
-                if is_normal_seat {
-                    format!("{seat:0>4}")
-                } else {
-                    format!("{seat:<4}")
-                }
-            } else {
-                "    ".into()
+            let seat = match leg.seat {
+                Some(ref seat) => seat.to_string(),
+                None           => "    ".into(),
             };
 
-            let seq = if let Some(seq) = leg.sequence {
-                format!("{:0>4}", seq)
+            if seat.len() != Field::SeatNumber.len() {
+                return Err(Error::from(ErrorKind::MandatoryDataSize)
+                    .with_field(Field::SeatNumber)
+                    .with_leg(Some(leg_index)))
+            }
+
+            let seq = leg.sequence.as_ref().map(|x| x.to_string()).unwrap_or_default();
+
+            if seq.len() > Field::CheckInSequenceNumber.len() {
+                return Err(Error::from(ErrorKind::MandatoryDataSize)
+                    .with_field(Field::CheckInSequenceNumber)
+                    .with_leg(Some(leg_index)))
+            }
+
+            // The first leg carries the version and the unique conditional fields.
+            let unique = if leg_index == 0 {
+                Some(self.build_unique()?)
             } else {
-                "    ".into()
+                None
             };
 
+            let conditional = build_conditional(self.version, unique.as_deref(), leg)
+                .map_err(|x| x.with_leg(Some(leg_index)))?;
 
-            ret = format!("{}{:<7}{:<3}{:<3}{:<3}{:<5}{:3}{:1}{:>4}{:<5}{:1}00",
+            ret = format!("{}{:<7}{:<3}{:<3}{:<3}{:<5}{:3}{:1}{:>4}{:<5}{:1}{}",
                 ret,
                 leg.pnr.as_deref().unwrap_or(""),
-                leg.src_airport.as_deref().unwrap_or(""),
-                leg.dst_airport.as_deref().unwrap_or(""),
-                leg.airline.as_deref().unwrap_or(""),
+                leg.src_airport.as_ref().map_or("", AirportCode::as_str),
+                leg.dst_airport.as_ref().map_or("", AirportCode::as_str),
+                leg.airline.as_ref().map_or("", AirlineDesignator::as_str),
                 leg.flight_number.as_deref().unwrap_or(""),
                 if let Some(ref day) = leg.flight_day {
                     format!("{:0>3}", day.ordinal())
@@ -305,248 +694,496 @@ impl Bcbp {
                 leg.compartment.unwrap_or(' '),
                 seat,
                 seq,
-                leg.pax_status.to_char());
+                leg.pax_status.to_char(),
+                build_sized(Field::FieldSizeOfVariableSizeField, &conditional)?);
         }
+
+        if self.security_data_type != SecurityDataType::None || self.security_data.is_some() {
+            let data = self.security_data.as_deref().unwrap_or("");
+
+            ret.push('^');
+            ret.push(self.security_data_type.to_char());
+            ret.push_str(&build_sized(Field::LengthOfSecurityData, data)?);
+        }
+
+        // Whatever was emitted must be readable back without any tolerance.
+        if mode == Mode::Strict {
+            Self::from_with_mode(&ret, Mode::Strict)?;
+        }
+
         Ok(ret)
     }
 
-    pub fn from(src: &str) -> BcbpResult<Bcbp> {
+    /// Checks that all the mandatory items are set, as required to build in `Mode::Strict`.
+    fn verify_mandatory(&self) -> BcbpResult<()> {
 
+        if !(1..=9).contains(&self.legs.len()) {
+            return Err(Error::from(ErrorKind::InvalidLegsCount).with_field(Field::NumberOfLegsEncoded))
+        }
 
-        // let src = src_data.as_ref();
+        let missing = if self.name_last.is_empty() {
+            Some((Field::PassengerName, None))
+        } else if self.ticket_flag == TicketIndicator::None {
+            Some((Field::ElectronicTicketIndicator, None))
+        } else {
+            self.legs
+                .iter()
+                .enumerate()
+                .find_map(|(idx, leg)| Some((leg.missing_mandatory()?, Some(idx))))
+        };
 
-        if !src.is_ascii() {
-            return Err(Error::InvalidCharacters);
+        if let Some((field, leg)) = missing {
+            return Err(Error::from(ErrorKind::MandatoryFieldMissing).with_field(field).with_leg(leg))
         }
 
-        let mut chunk = Chunk::new(src);
+        if self.version.is_none() {
+            let first = &self.legs[0];
+            let has_conditional =
+                !self.build_unique()?.is_empty() ||
+                !build_conditional(None, None, first)?.is_empty();
 
-        let src = src.to_uppercase();
-
-        if src.len() < 60 {
-            return Err(Error::MandatoryDataSize)
+            if has_conditional {
+                return Err(Error::from(ErrorKind::MandatoryFieldMissing).with_field(Field::VersionNumber))
+            }
         }
 
-        let code = chunk.fetch_char(Field::FormatCode)?;
+        Ok(())
+    }
 
-        if code != 'M' {
-            return Err(Error::InvalidFormatCode(code))
-        }
+    /// Encodes the unique conditional fields, dropping the trailing ones which are not set.
+    fn build_unique(&self) -> BcbpResult<String> {
+        let schema = self.version.unwrap_or(DEFAULT_VERSION);
+
+        build_section(schema.unique_fields(), &[
+            (Field::PassengerDescription, code_item(self.pax_type.to_char())),
+            (Field::SourceOfCheckIn, code_item(self.checkin_src.to_char())),
+            (Field::SourceOfBoardingPassIssuance, code_item(self.boardingpass_src.to_char())),
+            (Field::DateOfIssueOfBoardingPass, self.boardingpass_issued.as_ref().map(ToString::to_string)),
+            (Field::DocumentType, code_item(self.doc_type.to_char())),
+            (Field::AirlineDesignatorOfBoardingPassIssuer, self.boardingpass_airline.map(|x| x.to_string())),
+            (Field::BaggageTagLicensePlateNumbers, self.bagtag1.map(|x| x.to_string())),
+            (Field::FirstNonConsecutiveBaggageTagLicensePlateNumbers, self.bagtag2.map(|x| x.to_string())),
+            (Field::SecondNonConsecutiveBaggageTagLicensePlateNumbers, self.bagtag3.map(|x| x.to_string())),
+        ], self.unexpected_data.as_deref())
+    }
+
+    /// Parses a boarding pass in `Mode::Tolerant`.
+    pub fn from(src: &str) -> BcbpResult<Bcbp> {
+        Self::from_with_mode(src, Mode::Tolerant)
+    }
 
-        // The number of legs informs the breakdown of the various field iterators.
-        let legs = chunk.fetch_usize(Field::NumberOfLegsEncoded, 10)?;
+    /// Parses a boarding pass, validating the input as required by `mode`.
+    pub fn from_with_mode(src: &str, mode: Mode) -> BcbpResult<Bcbp> {
 
-        if !(1..=9).contains(&legs) {
-            return Err(Error::InvalidLegsCount)
+        if let Some(offset) = src.bytes().position(|x| !x.is_ascii()) {
+            return Err(Error::from(ErrorKind::InvalidCharacters).with_offset(offset));
         }
 
+        if src.len() < 60 {
+            return Err(Error::from(ErrorKind::MandatoryDataSize).with_offset(src.len()))
+        }
+
+        // A variable size field longer than the input is reported as such,
+        // rather than as the subsection too long of the raw parsing.
+        let mut pass = raw::Bcbp::default();
+        raw::parse(&mut pass, Chunk::with_mode(src, mode)).map_err(|e| match (e.kind(), e.field()) {
+            (ErrorKind::SubsectionTooLong, Some(Field::FieldSizeOfVariableSizeField)) => {
+                e.with_kind(ErrorKind::CoditionalDataSize)
+            }
+            _ => e,
+        })?;
+
         let mut bcbp = Bcbp::default();
+        Self::convert(&mut bcbp, &pass, Chunk::with_mode(src, mode))?;
 
+        Ok(bcbp)
+    }
 
-        let name = chunk.fetch_str(Field::PassengerName)?;
+    /// Parses a boarding pass as far as possible, collecting every problem found
+    /// instead of failing on the first one.
+    ///
+    /// The pass returned is the same `Bcbp::from()` returns, if none of the problems
+    /// is a [`Severity::Error`]. Characters outside of ASCII are replaced by `'\x1A'`.
+    pub fn diagnose(src: &str) -> (Bcbp, Vec<Diagnostic>) {
+
+        // Substituting every byte keeps the offsets of the diagnostics.
+        let src: String = src
+            .bytes()
+            .map(|x| if x.is_ascii() { x as char } else { chunk::SUBSTITUTE })
+            .collect();
+
+        let diagnostics = Rc::new(RefCell::new(Vec::new()));
+        let mut pass = raw::Bcbp::default();
+        let mut bcbp = Bcbp::default();
 
-        let (first, last) = bcbp_name(name);
-        bcbp.name_last   = first;
-        bcbp.name_first  = last;
-        bcbp.ticket_flag = match chunk.fetch_char(Field::ElectronicTicketIndicator)? {
-            ' ' => None,
-            c   => Some(c),
-        };
+        // The problem which stops the parsing is collected as well.
+        let _ = raw::parse(&mut pass, Chunk::with_diagnostics(&src, Rc::clone(&diagnostics)));
+        let _ = Self::convert(&mut bcbp, &pass, Chunk::with_diagnostics(&src, Rc::clone(&diagnostics)));
 
-        for leg_index in 0 .. legs {
+        // The fields are validated after the whole pass is scanned.
+        let mut diagnostics = diagnostics.take();
+        diagnostics.sort_by_key(|x| x.range.start);
+
+        (bcbp, diagnostics)
+    }
 
+    /// Converts the fields of `pass` into `bcbp`, which holds whatever was converted in
+    /// case of failure. The `chunk` reports the invalid fields, at the offsets they were
+    /// scanned at when `pass` was parsed.
+    fn convert(bcbp: &mut Bcbp, pass: &raw::Bcbp<'_>, mut chunk: Chunk) -> BcbpResult<()> {
+
+        let (last, first) = bcbp_name(&pass.pax_name);
+        bcbp.name_last   = last;
+        bcbp.name_first  = first;
+        bcbp.ticket_flag = pass.eticket_flag();
+        bcbp.version     = pass.version;
+
+        for (leg_index, src) in pass.legs.iter().enumerate() {
             let mut leg = Leg::default();
 
-            // Mandatory fields common to all legs.
-            leg.set_pnr(chunk.fetch_str(Field::OperatingCarrierPnrCode)?)?;
-            leg.set_src_airport(chunk.fetch_str(Field::FromCityAirportCode)?)?;
-            leg.set_dst_airport(chunk.fetch_str(Field::ToCityAirportCode)?)?;
-            leg.set_airline(chunk.fetch_str(Field::OperatingCarrierDesignator)?)?;
-            leg.set_flight_number(chunk.fetch_str(Field::FlightNumber)?)?;
+            chunk.set_leg(Some(leg_index));
+            let result = Self::convert_leg(bcbp, &mut leg, pass, src, &mut chunk, leg_index);
+            chunk.set_leg(None);
 
-            let flight_day = chunk.fetch_str(Field::DateOfFlight)?;
-            leg.flight_day = if !flight_day.trim().is_empty() {
-                Some(DayOfYear::new(u16_from_str_force(flight_day, 10)).unwrap())
-            } else {
-                None
-            };
+            bcbp.legs.push(leg);
+            result?;
+        }
 
-            leg.compartment   = match chunk.fetch_char(Field::CompartmentCode)? {
-                ' ' => None,
-                c   => Some(c),
-            };
+        bcbp.security_data_type = pass.security_data.type_of_security_data().unwrap_or_default();
+        bcbp.security_data      = pass.security_data.security_data().map(String::from);
 
-            leg.set_seat(chunk.fetch_str(Field::SeatNumber)?)?;
-            leg.sequence      = u32_from_str_opt(chunk
-                .fetch_str(Field::CheckInSequenceNumber)?, 10);
+        Ok(())
+    }
 
-            leg.pax_status    = PaxStatus::from_char(chunk.fetch_char(Field::PassengerStatus)?);
+    /// Converts the leg `src` at `leg_index` into `leg`. The first leg also carries the
+    /// unique conditional fields of `pass`, which are converted into `bcbp`.
+    fn convert_leg(
+        bcbp: &mut Bcbp,
+        leg: &mut Leg,
+        pass: &raw::Bcbp<'_>,
+        src: &raw::Leg<'_>,
+        chunk: &mut Chunk,
+        leg_index: usize,
+    ) -> BcbpResult<()> {
+
+        // Mandatory fields common to all legs.
+        chunk.check(Field::OperatingCarrierPnrCode, &src.offsets, &src.pnr, Severity::Error, |x| leg.set_pnr(x))?;
+
+        // Malformed airport codes and airline designators are kept as they are in `Mode::Tolerant`.
+        chunk.check(Field::FromCityAirportCode, &src.offsets, &src.src_airport, Severity::Warning, |x| {
+            leg.set_src_airport(x).inspect_err(|_| leg.src_airport = AirportCode::verbatim(x))
+        })?;
+        chunk.check(Field::ToCityAirportCode, &src.offsets, &src.dst_airport, Severity::Warning, |x| {
+            leg.set_dst_airport(x).inspect_err(|_| leg.dst_airport = AirportCode::verbatim(x))
+        })?;
+        chunk.check(Field::OperatingCarrierDesignator, &src.offsets, &src.airline, Severity::Warning, |x| {
+            leg.set_airline(x).inspect_err(|_| leg.airline = AirlineDesignator::verbatim(x))
+        })?;
+
+        chunk.check(Field::FlightNumber, &src.offsets, &src.flight_number, Severity::Error, |x| {
+            leg.set_flight_number(x)
+        })?;
+        chunk.check(Field::DateOfFlight, &src.offsets, &src.flight_day, Severity::Error, |x| {
+            leg.flight_day = flight_day_from_str(x)?;
+            Ok(())
+        })?;
 
-            // Field size of the variable size field that follows for the leg.
-            let conditional_size =
-                chunk.fetch_usize(Field::FieldSizeOfVariableSizeField, 16)?;
+        leg.compartment   = match src.compartment() {
+            ' ' => None,
+            c   => Some(c),
+        };
 
-            if conditional_size > chunk.len() {
-                return Err(Error::CoditionalDataSize)
-            }
+        chunk.check(Field::SeatNumber, &src.offsets, &src.seat, Severity::Error, |x| leg.set_seat(x))?;
 
-            if conditional_size > 0 {
-
-                // chunk over the entire set of conditional fields.
-                let mut conditional_item = chunk.fetch_chunk(conditional_size)?;
-
-                // The first leg may contain some optional fields at the root level.
-                if leg_index == 0 {
-
-                    // Validate the beginning of version number tag as a sanity check.
-                    let prefix = conditional_item.fetch_char(Field::BeginningOfVersionNumber)?;
-                    if prefix != '<' && prefix != '>' {
-                        return Err(Error::InvalidPrefix(Field::BeginningOfVersionNumber, prefix))
-                    }
-
-                    bcbp.version = Some(conditional_item.fetch_char(Field::VersionNumber)?);
-
-                    // Conditional unique fields are embedded in their own variable-length wrapper.
-                    if conditional_item.len() > 0 {
-                    let len = conditional_item
-                        .fetch_usize(Field::FieldSizeOfStructuredMessageUnique, 16)?;
-                    if len > 0 {
-                        let mut unique_chunk = conditional_item.fetch_chunk(len)?;
-
-                        bcbp.pax_type =
-                            unique_chunk
-                            .fetch_char_opt(Field::PassengerDescription)?
-                            .map(PaxType::from_char).unwrap_or_default();
-                        bcbp.checkin_src =
-                            unique_chunk.fetch_char_opt(Field::SourceOfCheckIn)?;
-                        bcbp.boardingpass_src = unique_chunk
-                            .fetch_char_opt(Field::SourceOfBoardingPassIssuance)?;
-                        bcbp.boardingpass_issued = unique_chunk
-                            .fetch_str_opt(Field::DateOfIssueOfBoardingPass)?
-                            .map(|x| u16_from_str_force(x, 10));
-                        bcbp.doc_type = unique_chunk.fetch_char_opt(Field::DocumentType)?;
-                        bcbp.set_boradingpass_airline(
-                            unique_chunk
-                            .fetch_str_opt(Field::AirlineDesignatorOfBoardingPassIssuer)?
-                            .unwrap_or("")
-                        )?;
-
-                        // let _ = unique_chunk
-                        //     .fetch_str_opt(Field::BaggageTagLicensePlateNumbers)?
-                        //     .map(|x| x.trim().into());
-                        // let _ =
-                        //     unique_chunk
-                        //         .fetch_str_opt(
-                        //             field::Field::FirstNonConsecutiveBaggageTagLicensePlateNumbers,
-                        //         )?
-                        //         .map(|x| x.trim().into());
-                        // let _ =
-                        //     unique_chunk
-                        //         .fetch_str_opt(
-                        //             field::Field::SecondNonConsecutiveBaggageTagLicensePlateNumbers,
-                        //         )?
-                        //        .map(|x| x.trim().into());
-                    }
+        // A value which is not a sequence number, such as `INF` for infants, is kept as it is
+        // in `Mode::Tolerant`.
+        chunk.check(Field::CheckInSequenceNumber, &src.offsets, &src.checkin_sequence, Severity::Warning, |x| {
+            match sequence_from_str(x) {
+                Ok(sequence) => {
+                    leg.sequence = sequence;
+                    Ok(())
                 }
-            }
-
-            // Conditional fields common to all legs.
-            if conditional_item.len() > 0 {
-                let len = conditional_item
-                    .fetch_usize(Field::FieldSizeOfStructuredMessageRepeated, 16)?;
-                if len > 0 {
-                    let mut repeated_chunk = conditional_item.fetch_chunk(len)?;
-
-                    leg.airline_num = repeated_chunk
-                        .fetch_str_opt(Field::AirlineNumericCode)?
-                        .map(|x| u16_from_str_force(x.trim(), 10));
-
-                    leg.set_doc_number(
-                        repeated_chunk
-                        .fetch_str_opt(Field::DocumentFormSerialNumber)?
-                        .unwrap_or("")
-                    )?;
-                    let _selectee_indicator =
-                        repeated_chunk.fetch_char_opt(Field::SelecteeIndicator)?;
-                    let _international_document_verification = repeated_chunk
-                        .fetch_char_opt(Field::InternationalDocumentVerification)?;
-                    leg.set_marketing_airline(
-                        repeated_chunk
-                        .fetch_str_opt(Field::MarketingCarrierDesignator)?
-                        .unwrap_or("")
-                    )?;
-                    leg.set_frequent_flyer_airline(
-                        repeated_chunk
-                        .fetch_str_opt(Field::FrequentFlyerAirlineDesignator)?
-                        .unwrap_or("")
-                    )?;
-                    leg.set_frequent_flyer_numbder(
-                        repeated_chunk
-                        .fetch_str_opt(Field::FrequentFlyerNumber)?
-                        .unwrap_or("")
-                    )?;
-                    let _id_ad_indicator =
-                        repeated_chunk.fetch_char_opt(Field::IdAdIndicator)?;
-                    leg.set_bag_allowance(
-                        repeated_chunk
-                        .fetch_str_opt(Field::FreeBaggageAllowance)?
-                        .unwrap_or("")
-                    )?;
-                    leg.fast_track = repeated_chunk.fetch_char_opt(Field::FastTrack)?;
+                Err(error) => {
+                    leg.sequence = CheckInSequence::other(x).ok();
+                    Err(error)
                 }
             }
+        })?;
 
-                // Any remaining text is ascribed to airline use.
-                if conditional_item.len() > 0 {
-                    let len  = conditional_item.len();
-                    let body = conditional_item
-                        .fetch_str_len(Field::AirlineIndividualUse, len)?;
-                    leg.var = Some(body.into());
-                }
+        leg.pax_status    = src.pax_status();
 
-            }
+        // The unique conditional fields precede the repeated ones of the first leg.
+        if leg_index == 0 {
+            chunk.set_leg(None);
+            Self::convert_unique(bcbp, pass, chunk)?;
+            chunk.set_leg(Some(leg_index));
+        }
 
-            bcbp.legs.push(leg);
+        // Conditional fields common to all legs.
+        if let Some(ref x) = src.airline_numeric_code {
+            chunk.check(Field::AirlineNumericCode, &src.offsets, x, Severity::Error, |x| {
+                leg.airline_num = airline_num_from_str(x)?;
+                Ok(())
+            })?;
+        }
+        if let Some(ref x) = src.document_form_serial_number {
+            chunk.check(Field::DocumentFormSerialNumber, &src.offsets, x, Severity::Error, |x| leg.set_doc_number(x))?;
+        }
+        if let Some(ref x) = src.selectee_indicator {
+            chunk.check(Field::SelecteeIndicator, &src.offsets, x, Severity::Error, |x| {
+                leg.set_selectee(SelecteeIndicator::from_char(raw::code(x)))
+            })?;
+        }
+        if let Some(ref x) = src.international_document_verification {
+            chunk.check(Field::InternationalDocumentVerification, &src.offsets, x, Severity::Error, |x| {
+                leg.set_doc_verification(DocumentVerification::from_char(raw::code(x)))
+            })?;
+        }
+        if let Some(ref x) = src.marketing_carrier_designator {
+            chunk.check(Field::MarketingCarrierDesignator, &src.offsets, x, Severity::Warning, |x| {
+                leg.set_marketing_airline(x).inspect_err(|_| leg.marketing_airline = AirlineDesignator::verbatim(x))
+            })?;
+        }
+        if let Some(ref x) = src.frequent_flyer_airline {
+            chunk.check(Field::FrequentFlyerAirlineDesignator, &src.offsets, x, Severity::Warning, |x| {
+                leg.set_frequent_flyer_airline(x).inspect_err(|_| leg.frequent_flyer_airline = AirlineDesignator::verbatim(x))
+            })?;
+        }
+        if let Some(ref x) = src.frequent_flyer_number {
+            chunk.check(Field::FrequentFlyerNumber, &src.offsets, x, Severity::Error, |x| {
+                leg.set_frequent_flyer_numbder(x)
+            })?;
+        }
+        if let Some(ref x) = src.id_ad_indicator {
+            chunk.check(Field::IdAdIndicator, &src.offsets, x, Severity::Error, |x| {
+                leg.set_id_ad(IdAdIndicator::from_char(raw::code(x)))
+            })?;
+        }
+        if let Some(ref x) = src.free_baggage_allowance {
+            chunk.check(Field::FreeBaggageAllowance, &src.offsets, x, Severity::Error, |x| leg.set_bag_allowance(x))?;
         }
 
-        // Remaining input is ascribed to Security Data.
-        if chunk.len() > 0 {
+        leg.fast_track      = src.fast_track().unwrap_or_default();
+        leg.unexpected_data = src.unexpected_data.as_deref().map(String::from);
+        leg.var             = src.airline_individual_use.as_deref().map(String::from);
 
-            let prefix = chunk.fetch_char(Field::BeginningOfSecurityData)?;
-            if prefix != '^' {
-                return Err(Error::InvalidPrefix(Field::BeginningOfSecurityData, prefix))
-            }
+        Ok(())
+    }
+
+    /// Converts the unique conditional fields of `pass` into `bcbp`.
+    fn convert_unique(bcbp: &mut Bcbp, pass: &raw::Bcbp<'_>, chunk: &Chunk) -> BcbpResult<()> {
+
+        bcbp.pax_type         = pass.pax_description().unwrap_or_default();
+        bcbp.checkin_src      = pass.source_of_check_in().unwrap_or_default();
+        bcbp.boardingpass_src = pass.source_of_boarding_pass_issuance().unwrap_or_default();
+
+        // An invalid date is dropped in `Mode::Tolerant`.
+        if let Some(ref x) = pass.date_of_issue_of_boarding_pass {
+            chunk.check(Field::DateOfIssueOfBoardingPass, &pass.offsets, x, Severity::Warning, |x| {
+                bcbp.boardingpass_issued = match x.trim() {
+                    "" => None,
+                    x  => Some(x.parse()?),
+                };
+                Ok(())
+            })?;
+        }
 
-            // The security data type captured as a separate field set as the next field, data length, is discarded.
-            bcbp.security_data_type = chunk.fetch_char_opt(Field::TypeOfSecurityData)?;
+        bcbp.doc_type = pass.doc_type().unwrap_or_default();
 
-            // Scan the length of the security data.
-            if chunk.len() > 0 {
-                let len = chunk.fetch_usize(Field::LengthOfSecurityData, 16)?;
-                if len > 0 {
-                    let body = chunk.fetch_str_len(Field::SecurityData, len)?;
-                    bcbp.security_data = Some(body.into());
-                }
-            }
+        if let Some(ref x) = pass.airline_designator_of_boarding_pass_issuer {
+            chunk.check(Field::AirlineDesignatorOfBoardingPassIssuer, &pass.offsets, x, Severity::Warning, |x| {
+                bcbp.set_boradingpass_airline(x).inspect_err(|_| bcbp.boardingpass_airline = AirlineDesignator::verbatim(x))
+            })?;
+        }
+        if let Some(ref x) = pass.baggage_tag_license_plate_numbers {
+            chunk.check(Field::BaggageTagLicensePlateNumbers, &pass.offsets, x, Severity::Error, |x| {
+                bcbp.set_bagtag1(x)
+            })?;
+        }
+        if let Some(ref x) = pass.first_non_consecutive_baggage_tag_license_plate_numbers {
+            chunk.check(Field::FirstNonConsecutiveBaggageTagLicensePlateNumbers, &pass.offsets, x, Severity::Error, |x| {
+                bcbp.set_bagtag2(x)
+            })?;
+        }
+        if let Some(ref x) = pass.second_non_consecutive_baggage_tag_license_plate_numbers {
+            chunk.check(Field::SecondNonConsecutiveBaggageTagLicensePlateNumbers, &pass.offsets, x, Severity::Error, |x| {
+                bcbp.set_bagtag3(x)
+            })?;
         }
 
-        if !chunk.eof() {
-            Err(Error::TrailingData)
-        } else {
-            Ok(bcbp)
+        bcbp.unexpected_data = pass.unexpected_data.as_deref().map(String::from);
+
+        Ok(())
+    }
+}
+
+/// Validates the fields of a pass parsed with [`raw::Bcbp::from()`], in `Mode::Tolerant`.
+///
+/// ```
+/// use iata::bcbp::{Bcbp, raw};
+///
+/// let src = "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100";
+/// let pass = Bcbp::try_from(raw::Bcbp::from(src).unwrap()).unwrap();
+///
+/// assert_eq!(pass, Bcbp::from(src).unwrap());
+/// ```
+impl TryFrom<raw::Bcbp<'_>> for Bcbp {
+    type Error = Error;
+
+    fn try_from(pass: raw::Bcbp<'_>) -> BcbpResult<Self> {
+        let mut bcbp = Bcbp::default();
+        Self::convert(&mut bcbp, &pass, Chunk::new(""))?;
+
+        Ok(bcbp)
+    }
+}
+
+/// Encodes the variable size field of a leg. The first leg passes the encoded
+/// unique section, which is prefixed with the version number.
+fn build_conditional(version: Option<BcbpVersion>, unique: Option<&str>, leg: &Leg) -> BcbpResult<String> {
+
+    let schema = version.unwrap_or(DEFAULT_VERSION);
+
+    let repeated = build_section(schema.repeated_fields(), &[
+        (Field::AirlineNumericCode, leg.airline_num.map(|x| format!("{:03}", x))),
+        (Field::DocumentFormSerialNumber, leg.doc_number.clone()),
+        (Field::SelecteeIndicator, code_item(leg.selectee.to_char())),
+        (Field::InternationalDocumentVerification, code_item(leg.doc_verification.to_char())),
+        (Field::MarketingCarrierDesignator, leg.marketing_airline.map(|x| x.to_string())),
+        (Field::FrequentFlyerAirlineDesignator, leg.frequent_flyer_airline.map(|x| x.to_string())),
+        (Field::FrequentFlyerNumber, leg.frequent_flyer_number.clone()),
+        (Field::IdAdIndicator, code_item(leg.id_ad.to_char())),
+        (Field::FreeBaggageAllowance, leg.bag_allowance.clone()),
+        (Field::FastTrack, code_item(leg.fast_track.to_char())),
+    ], leg.unexpected_data.as_deref())?;
+
+    let var = leg.var.as_deref().unwrap_or("");
+
+    // Passes predating the version number carry no marker and no structured sections.
+    if schema == BcbpVersion::Legacy {
+        return Ok(format!("{}{}{}", unique.unwrap_or(""), repeated, var))
+    }
+
+    let mut ret = String::new();
+
+    if let Some(unique) = unique {
+        if version.is_none() && unique.is_empty() && repeated.is_empty() && var.is_empty() {
+            return Ok(ret)
         }
+
+        // The size of the unique section always follows the version number.
+        ret.push('>');
+        ret.push(schema.to_char());
+        ret.push_str(&build_sized(Field::FieldSizeOfStructuredMessageUnique, unique)?);
+    }
+
+    if !repeated.is_empty() || !var.is_empty() {
+        ret.push_str(&build_sized(Field::FieldSizeOfStructuredMessageRepeated, &repeated)?);
+        ret.push_str(var);
     }
+
+    Ok(ret)
 }
 
-fn u16_from_str_force(src: &str, radix: u32) -> u16 {
-    match u16::from_str_radix(src.trim().trim_start_matches('0'), radix) {
-        Ok(v) => v,
-        _     => 0,
+/// Concatenates the fixed-length fields of a conditional section, padding each with spaces.
+/// Only the `fields` defined by the version are written. Fields which are not set at
+/// the end of the section are omitted, unless `extra` data has to follow them.
+fn build_section(
+    fields: &[Field],
+    items: &[(Field, Option<String>)],
+    extra: Option<&str>,
+) -> BcbpResult<String> {
+
+    let items: Vec<_> = items
+        .iter()
+        .filter(|(field, _)| fields.contains(field))
+        .collect();
+
+    let used = if extra.is_some() {
+        items.len()
+    } else {
+        items
+            .iter()
+            .rposition(|(_, value)| value.is_some())
+            .map_or(0, |idx| idx + 1)
+    };
+
+    let mut ret = String::new();
+
+    for (field, value) in &items[..used] {
+        let value = value.as_deref().unwrap_or("");
+
+        if value.len() > field.len() {
+            return Err(Error::from(ErrorKind::CoditionalDataSize).with_field(*field))
+        }
+
+        ret.push_str(&format!("{:<width$}", value, width = field.len()));
+    }
+
+    ret.push_str(extra.unwrap_or(""));
+
+    Ok(ret)
+}
+
+/// Encodes a coded item of a conditional section, not set if blank.
+fn code_item(code: char) -> Option<String> {
+    (code != ' ').then(|| code.to_string())
+}
+
+/// Prefixes `body` with its length encoded as two hexadecimal digits.
+fn build_sized(field: Field, body: &str) -> BcbpResult<String> {
+
+    debug_assert_eq!(field.len(), 2);
+
+    if body.len() > 0xFF {
+        return Err(Error::from(ErrorKind::CoditionalDataSize).with_field(field))
     }
+
+    Ok(format!("{:02X}{}", body.len(), body))
 }
 
-fn u32_from_str_opt(src: &str, radix: u32) -> Option<u16> {
-    u16::from_str_radix(src.trim().trim_start_matches('0'), radix).ok()
+/// Parses the day of the year of a flight, blank if not set.
+fn flight_day_from_str(src: &str) -> BcbpResult<Option<DayOfYear>> {
+    let src = src.trim();
+
+    if src.is_empty() {
+        return Ok(None)
+    }
+
+    if !src.bytes().all(|x| x.is_ascii_digit()) {
+        return Err(ErrorKind::DigitsExpected.into())
+    }
+
+    Ok(Some(DayOfYear::new(u16_from_str_force(src, 10))?))
+}
+
+fn airline_num_from_str(src: &str) -> BcbpResult<Option<u16>> {
+    let src = src.trim();
+
+    if src.is_empty() {
+        return Ok(None)
+    }
+
+    if !src.bytes().all(|x| x.is_ascii_digit()) {
+        return Err(ErrorKind::DigitsExpected.into())
+    }
+
+    // At most 3 digits, which always fit.
+    Ok(Some(src.parse().unwrap()))
+}
+
+fn u16_from_str_force(src: &str, radix: u32) -> u16 {
+    u16::from_str_radix(src.trim().trim_start_matches('0'), radix).unwrap_or_default()
+}
+
+fn sequence_from_str(src: &str) -> BcbpResult<Option<CheckInSequence>> {
+    let src = src.trim();
+
+    if src.is_empty() {
+        return Ok(None)
+    }
+
+    Ok(Some(src.parse()?))
 }
 
 
@@ -0,0 +1,241 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::bcbp_name;
+use super::error::{Error, ErrorKind};
+use super::translit::transliterate;
+
+/// Length of the passenger name item.
+const NAME_LEN: usize = 20;
+
+/// A title following the given names of a passenger, as in `BRUNER/ROMAN MR`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub enum Title {
+    Mr,
+    Mrs,
+    Ms,
+    Miss,
+    Mstr,
+    Dr,
+    Prof,
+    Rev,
+}
+
+impl Title {
+
+    /// The title as written in the name of a passenger.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Title::Mr   => "MR",
+            Title::Mrs  => "MRS",
+            Title::Ms   => "MS",
+            Title::Miss => "MISS",
+            Title::Mstr => "MSTR",
+            Title::Dr   => "DR",
+            Title::Prof => "PROF",
+            Title::Rev  => "REV",
+        }
+    }
+}
+
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Title {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "MR"   => Ok(Title::Mr),
+            "MRS"  => Ok(Title::Mrs),
+            "MS"   => Ok(Title::Ms),
+            "MISS" => Ok(Title::Miss),
+            "MSTR" => Ok(Title::Mstr),
+            "DR"   => Ok(Title::Dr),
+            "PROF" => Ok(Title::Prof),
+            "REV"  => Ok(Title::Rev),
+            _      => Err(ErrorKind::InvalidFormat.into()),
+        }
+    }
+}
+
+/// The name of a passenger split into its parts, as encoded in item 11.
+///
+/// The item holds up to 20 characters in the form `SURNAME/GIVEN NAMES TITLE`,
+/// so longer names are cut. A name filling the whole item may have been cut.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub struct PassengerName {
+    surname: String,
+    given_names: Option<String>,
+    title: Option<Title>,
+    truncated: bool,
+}
+
+impl PassengerName {
+
+    /// Constructs a name from its parts. Empty given names are not set.
+    pub fn new(surname: &str, given_names: Option<&str>, title: Option<Title>) -> Self {
+        Self {
+            surname: surname.trim().to_owned(),
+            given_names: given_names.map(str::trim).filter(|x| !x.is_empty()).map(str::to_owned),
+            title,
+            truncated: false,
+        }
+    }
+
+    /// Splits the name item of a pass. A title is recognized when it is the last
+    /// word of the given names.
+    ///
+    /// ```
+    /// use iata::bcbp::{PassengerName, Title};
+    ///
+    /// let name = PassengerName::from_bcbp("BRUNER/ROMAN MR     ");
+    ///
+    /// assert_eq!(name.surname(),     "BRUNER");
+    /// assert_eq!(name.given_names(), Some("ROMAN"));
+    /// assert_eq!(name.title(),       Some(Title::Mr));
+    /// assert!(!name.is_possibly_truncated());
+    /// ```
+    pub fn from_bcbp(src: &str) -> Self {
+        let truncated = src.trim_end().chars().count() >= NAME_LEN;
+
+        let (surname, given) = bcbp_name(src);
+        let given = given.as_deref().unwrap_or("").trim();
+
+        let (given, title) = match given.rsplit_once(' ') {
+            Some((rest, last)) => match last.parse() {
+                Ok(title) => (rest, Some(title)),
+                Err(_)    => (given, None),
+            },
+            None => match given.parse() {
+                Ok(title) => ("", Some(title)),
+                Err(_)    => (given, None),
+            },
+        };
+
+        Self {
+            truncated,
+            ..Self::new(&surname, Some(given), title)
+        }
+    }
+
+    /// The family name.
+    pub fn surname(&self) -> &str {
+        &self.surname
+    }
+
+    /// The given names, without the title.
+    pub fn given_names(&self) -> Option<&str> {
+        self.given_names.as_deref()
+    }
+
+    /// The title following the given names.
+    pub fn title(&self) -> Option<Title> {
+        self.title
+    }
+
+    /// Returns `true` if the name fills the whole item of the pass,
+    /// in which case the end of the name may be missing.
+    pub fn is_possibly_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Writes the name for the item 11 of a pass, transliterated with [`transliterate()`]
+    /// and shortened to 20 characters.
+    ///
+    /// A name which does not fit loses its title first, then the given names are
+    /// reduced to their initials, the middle ones before the first. The surname is
+    /// kept whole as long as possible.
+    ///
+    /// ```
+    /// use iata::bcbp::{PassengerName, Title};
+    ///
+    /// let name = PassengerName::new("Müller", Some("José María"), Some(Title::Mr));
+    ///
+    /// assert_eq!(name.to_bcbp(), "MUELLER/JOSE MARIA");
+    ///
+    /// let name = PassengerName::new("Papadopoulos", Some("Alexandros Ioannis"), None);
+    ///
+    /// assert_eq!(name.to_bcbp(), "PAPADOPOULOS/A I");
+    /// ```
+    pub fn to_bcbp(&self) -> String {
+        let surname = transliterate(&self.surname);
+        let given = self.given_names.as_deref().map(transliterate).unwrap_or_default();
+        let title = self.title.map(|x| x.as_str());
+
+        let full: Vec<&str> = given.split_whitespace().collect();
+        let initials: Vec<&str> = full.iter().map(|x| &x[..1]).collect();
+
+        let middle = full.iter().take(1).chain(initials.iter().skip(1)).copied().collect::<Vec<_>>();
+
+        let candidates = [
+            join_name(&surname, &full, title),
+            join_name(&surname, &full, None),
+            join_name(&surname, &middle, None),
+            join_name(&surname, &initials, None),
+            join_name(&surname, &initials[..initials.len().min(1)], None),
+        ];
+
+        match candidates.iter().find(|x| x.len() <= NAME_LEN) {
+            Some(name) => name.clone(),
+            None => {
+                let mut name = candidates[candidates.len() - 1].clone();
+                name.truncate(NAME_LEN);
+                name
+            },
+        }
+    }
+}
+
+/// Joins the parts in the form `SURNAME/GIVEN NAMES TITLE`.
+fn join_name(surname: &str, given: &[&str], title: Option<&str>) -> String {
+    let mut ret = surname.to_owned();
+
+    if given.is_empty() && title.is_none() {
+        return ret
+    }
+
+    ret.push('/');
+    ret.push_str(&given.join(" "));
+
+    if let Some(title) = title {
+        if !given.is_empty() {
+            ret.push(' ');
+        }
+        ret.push_str(title);
+    }
+
+    ret
+}
+
+/// Writes the form `SURNAME/GIVEN NAMES TITLE`, which may exceed the length of the item.
+impl fmt::Display for PassengerName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.surname)?;
+
+        if self.given_names.is_none() && self.title.is_none() {
+            return Ok(())
+        }
+
+        f.write_str("/")?;
+
+        if let Some(ref given_names) = self.given_names {
+            f.write_str(given_names)?;
+
+            if self.title.is_some() {
+                f.write_str(" ")?;
+            }
+        }
+
+        if let Some(title) = self.title {
+            write!(f, "{}", title)?;
+        }
+
+        Ok(())
+    }
+}
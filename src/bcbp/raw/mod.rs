@@ -4,39 +4,115 @@
 // of the MIT license.  See the LICENSE file for details.
 
 mod parser;
-
-use crate::bcbp::error::BcbpResult;
+mod writer;
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use time::Date;
+
+use crate::bcbp::field::Field;
+use crate::bcbp::error::{Error, ErrorKind, BcbpResult};
+use crate::bcbp::version::BcbpVersion;
+use crate::bcbp::codes::{
+    TicketIndicator,
+    CheckInSource,
+    BoardingPassSource,
+    DocumentType,
+    SelecteeIndicator,
+    DocumentVerification,
+    IdAdIndicator,
+    FastTrack,
+    SecurityDataType,
+};
+use crate::bcbp::{PaxStatus, PaxType, FlightDesignator, TicketNumber, PassengerName};
+use crate::datetime::{JulianIssueDate, Error as DateError};
 
 use parser::from_str;
 
-// Copyright (C) 2018 Martin Mroz
-//
-// This software may be modified and distributed under the terms
-// of the MIT license.  See the LICENSE file for details.
+pub(crate) use parser::parse;
+#[cfg(feature = "with-security")]
+pub(crate) use writer::SignedData;
+
+/// The character of a single character field. A field the input ended before
+/// reads as a space, not set.
+pub(crate) fn code(src: &str) -> char {
+    src.chars().next().unwrap_or(' ')
+}
+
+/// Takes ownership of a field borrowing the input.
+fn owned(src: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(src.into_owned())
+}
+
+/// Pads the `value` of the `field` with spaces to the length of the field, so that
+/// replacing a field keeps the rest of the pass in place. A longer value fails with `kind`.
+fn fit(field: Field, value: &str, kind: ErrorKind) -> BcbpResult<Cow<'static, str>> {
+    if !value.is_ascii() {
+        return Err(Error::from(ErrorKind::InvalidCharacters).with_field(field))
+    }
+
+    if value.len() > field.len() {
+        return Err(Error::from(kind).with_field(field))
+    }
+
+    Ok(Cow::Owned(format!("{:<width$}", value, width = field.len())))
+}
+
+macro_rules! gen_set {
+    (set $method_name:ident for $field_name:ident as $field:ident) => {
+        pub fn $method_name(&mut self, value: &str) -> BcbpResult<()> {
+            self.$field_name = fit(Field::$field, value, ErrorKind::MandatoryDataSize)?;
+            Ok(())
+        }
+    };
+    (set $method_name:ident for $field_name:ident as conditional $field:ident) => {
+        pub fn $method_name(&mut self, value: &str) -> BcbpResult<()> {
+            if self.$field_name.is_none() {
+                return Err(Error::from(ErrorKind::CoditionalData).with_field(Field::$field))
+            }
+
+            self.$field_name = Some(fit(Field::$field, value, ErrorKind::CoditionalDataSize)?);
+            Ok(())
+        }
+    };
+}
+
+/// Offsets of the fields in the input the pass was parsed from, by which their problems
+/// are located. A replaced field keeps its length, and so do the fields after it their offset.
+pub(crate) type Offsets = BTreeMap<Field, usize>;
 
 #[derive(Clone,Eq,PartialEq,Hash,Debug,Default)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
 pub struct Leg<'a> {
-    pub(crate) pnr: &'a str,
-    pub(crate) src_airport: &'a str,
-    pub(crate) dst_airport: &'a str,
-    pub(crate) airline: &'a str,
-    pub(crate) flight_number: &'a str,
-    pub(crate) flight_day: &'a str,
-    pub(crate) compartment: char,
-    pub(crate) seat: &'a str,
-    pub(crate) checkin_sequence: &'a str,
-    pub(crate) pax_status: char,
-    pub(crate) airline_numeric_code: Option<&'a str>,
-    pub(crate) document_form_serial_number: Option<&'a str>,
-    pub(crate) selectee_indicator: Option<char>,
-    pub(crate) international_document_verification: Option<char>,
-    pub(crate) marketing_carrier_designator: Option<&'a str>,
-    pub(crate) frequent_flyer_airline: Option<&'a str>,
-    pub(crate) frequent_flyer_number: Option<&'a str>,
-    pub(crate) id_ad_indicator: Option<char>,
-    pub(crate) free_baggage_allowance: Option<&'a str>,
-    pub(crate) fast_track: Option<char>,
-    pub(crate) airline_individual_use: Option<&'a str>,
+    pub(crate) pnr: Cow<'a, str>,
+    pub(crate) src_airport: Cow<'a, str>,
+    pub(crate) dst_airport: Cow<'a, str>,
+    pub(crate) airline: Cow<'a, str>,
+    pub(crate) flight_number: Cow<'a, str>,
+    pub(crate) flight_day: Cow<'a, str>,
+    pub(crate) compartment: Cow<'a, str>,
+    pub(crate) seat: Cow<'a, str>,
+    pub(crate) checkin_sequence: Cow<'a, str>,
+    pub(crate) pax_status: Cow<'a, str>,
+    /// Field size of the variable size field, as written.
+    pub(crate) conditional_size: Cow<'a, str>,
+    /// Field size of the conditional repeated section, as written.
+    pub(crate) repeated_size: Option<Cow<'a, str>>,
+    pub(crate) airline_numeric_code: Option<Cow<'a, str>>,
+    pub(crate) document_form_serial_number: Option<Cow<'a, str>>,
+    pub(crate) selectee_indicator: Option<Cow<'a, str>>,
+    pub(crate) international_document_verification: Option<Cow<'a, str>>,
+    pub(crate) marketing_carrier_designator: Option<Cow<'a, str>>,
+    pub(crate) frequent_flyer_airline: Option<Cow<'a, str>>,
+    pub(crate) frequent_flyer_number: Option<Cow<'a, str>>,
+    pub(crate) id_ad_indicator: Option<Cow<'a, str>>,
+    pub(crate) free_baggage_allowance: Option<Cow<'a, str>>,
+    pub(crate) fast_track: Option<Cow<'a, str>>,
+    pub(crate) unexpected_data: Option<Cow<'a, str>>,
+    pub(crate) airline_individual_use: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub(crate) offsets: Offsets,
 }
 
 impl Leg<'_> {
@@ -88,11 +164,11 @@ impl Leg<'_> {
         self.frequent_flyer_number.as_deref()
     }
 
+    /// The kind of industry discount of the ticket.
     /// Values are defined in Resolution 792.
-    /// Spaces indicate the field is not set.
-    /// Any other values are invalid.
-    pub fn id_ad_indicator(&self) -> Option<char> {
-        self.id_ad_indicator
+    /// A space indicates the field is not set.
+    pub fn id_ad_indicator(&self) -> Option<IdAdIndicator> {
+        self.id_ad_indicator.as_deref().map(code).map(IdAdIndicator::from_char)
     }
 
     /// Airline code of the operating carrier, which can be the same as the marketing carrier.
@@ -111,6 +187,12 @@ impl Leg<'_> {
         &self.flight_number
     }
 
+    /// The flight designator of the operating carrier, combining `airline` and `flight_number`.
+    /// Not set if either is invalid.
+    pub fn flight_designator(&self) -> Option<FlightDesignator> {
+        FlightDesignator::from_bcbp(&format!("{}{}", self.airline, self.flight_number)).ok()
+    }
+
     /// The Julian date code for the flight. The 3-digit number reflects the
     /// day of the year beginning with '0'. The year is to be inferred.
     /// Spaces indicate the field is not set.
@@ -123,7 +205,7 @@ impl Leg<'_> {
     /// A space indicates the field is not set.
     /// Any other values are invalid.
     pub fn compartment(&self) -> char {
-        self.compartment
+        code(&self.compartment)
     }
 
     /// Seat number of the passenger.
@@ -144,8 +226,8 @@ impl Leg<'_> {
     /// The status of the passenger.
     /// Field values are defined in Resolution 792.
     /// A space indicates the field is not set.
-    pub fn pax_status(&self) -> char {
-        self.pax_status
+    pub fn pax_status(&self) -> PaxStatus {
+        PaxStatus::from_char(code(&self.pax_status))
     }
 
     /// The three-digit airline numeric code.
@@ -162,26 +244,33 @@ impl Leg<'_> {
         self.document_form_serial_number.as_deref()
     }
 
+    /// The electronic ticket number, combining `airline_numeric_code` and
+    /// `document_form_serial_number`. Not set if either is invalid.
+    pub fn ticket_number(&self) -> Option<TicketNumber> {
+        TicketNumber::from_parts(self.airline_numeric_code.as_deref()?, self.document_form_serial_number.as_deref()?).ok()
+    }
+
     /// This field is used by certain agencies to demarcate individuals requiring extra screening.
     /// Although a conditional field, it is now required as of Resolotion 792 Version 6 when
     /// travel involves the United States. Values '0', '1', or '3' determine the type
     /// of screening the passenger will receive at US airports.
     /// A space indicates the field is not set.
-    pub fn selectee_indicator(&self) -> Option<char> {
-        self.selectee_indicator
+    pub fn selectee_indicator(&self) -> Option<SelecteeIndicator> {
+        self.selectee_indicator.as_deref().map(code).map(SelecteeIndicator::from_char)
     }
 
     /// This field is used by carriers to identify passengers requiring document verification.
     /// Connected to the display of the 'DOCS OK' string on international boarding passes.
-    pub fn international_document_verification(&self) -> Option<char> {
-        self.international_document_verification
+    /// A space indicates the field is not set.
+    pub fn international_document_verification(&self) -> Option<DocumentVerification> {
+        self.international_document_verification.as_deref().map(code).map(DocumentVerification::from_char)
     }
 
     /// Indicates if the passenger is eligible for fast track.
     /// If 'Y', the passenger is eligible, 'N' if not, ' ' if not set.
     /// Any other values are invalid.
-    pub fn fast_track(&self) -> Option<char> {
-        self.fast_track
+    pub fn fast_track(&self) -> Option<FastTrack> {
+        self.fast_track.as_deref().map(code).map(FastTrack::from_char)
     }
 
     /// Three characters, unstructured, left-aligned and space padded,
@@ -197,19 +286,82 @@ impl Leg<'_> {
         self.airline_individual_use.as_deref()
     }
 
+    /// Data at the end of the conditional repeated section which follows the fields
+    /// defined by the version of the boarding pass.
+    /// It is kept apart rather than read as fields the version does not know of.
+    pub fn unexpected_data(&self) -> Option<&str> {
+        self.unexpected_data.as_deref()
+    }
+
+    gen_set!(set set_pnr for pnr as OperatingCarrierPnrCode);
+    gen_set!(set set_src_airport for src_airport as FromCityAirportCode);
+    gen_set!(set set_dst_airport for dst_airport as ToCityAirportCode);
+    gen_set!(set set_airline for airline as OperatingCarrierDesignator);
+    gen_set!(set set_flight_number for flight_number as FlightNumber);
+    gen_set!(set set_flight_day for flight_day as DateOfFlight);
+    gen_set!(set set_compartment for compartment as CompartmentCode);
+    gen_set!(set set_seat for seat as SeatNumber);
+    gen_set!(set set_checkin_sequence for checkin_sequence as CheckInSequenceNumber);
+    gen_set!(set set_pax_status for pax_status as PassengerStatus);
+    gen_set!(set set_airline_numeric_code for airline_numeric_code as conditional AirlineNumericCode);
+    gen_set!(set set_document_form_serial_number for document_form_serial_number as conditional DocumentFormSerialNumber);
+    gen_set!(set set_selectee_indicator for selectee_indicator as conditional SelecteeIndicator);
+    gen_set!(set set_international_document_verification for international_document_verification as conditional InternationalDocumentVerification);
+    gen_set!(set set_marketing_carrier_designator for marketing_carrier_designator as conditional MarketingCarrierDesignator);
+    gen_set!(set set_frequent_flyer_airline for frequent_flyer_airline as conditional FrequentFlyerAirlineDesignator);
+    gen_set!(set set_frequent_flyer_number for frequent_flyer_number as conditional FrequentFlyerNumber);
+    gen_set!(set set_id_ad_indicator for id_ad_indicator as conditional IdAdIndicator);
+    gen_set!(set set_free_baggage_allowance for free_baggage_allowance as conditional FreeBaggageAllowance);
+    gen_set!(set set_fast_track for fast_track as conditional FastTrack);
+
+    /// Copies the fields borrowing the input, so that the leg outlives it.
+    pub fn into_owned(self) -> Leg<'static> {
+        Leg {
+            pnr:                                 owned(self.pnr),
+            src_airport:                         owned(self.src_airport),
+            dst_airport:                         owned(self.dst_airport),
+            airline:                             owned(self.airline),
+            flight_number:                       owned(self.flight_number),
+            flight_day:                          owned(self.flight_day),
+            compartment:                         owned(self.compartment),
+            seat:                                owned(self.seat),
+            checkin_sequence:                    owned(self.checkin_sequence),
+            pax_status:                          owned(self.pax_status),
+            conditional_size:                    owned(self.conditional_size),
+            repeated_size:                       self.repeated_size.map(owned),
+            airline_numeric_code:                self.airline_numeric_code.map(owned),
+            document_form_serial_number:         self.document_form_serial_number.map(owned),
+            selectee_indicator:                  self.selectee_indicator.map(owned),
+            international_document_verification: self.international_document_verification.map(owned),
+            marketing_carrier_designator:        self.marketing_carrier_designator.map(owned),
+            frequent_flyer_airline:              self.frequent_flyer_airline.map(owned),
+            frequent_flyer_number:               self.frequent_flyer_number.map(owned),
+            id_ad_indicator:                     self.id_ad_indicator.map(owned),
+            free_baggage_allowance:              self.free_baggage_allowance.map(owned),
+            fast_track:                          self.fast_track.map(owned),
+            unexpected_data:                     self.unexpected_data.map(owned),
+            airline_individual_use:              self.airline_individual_use.map(owned),
+            offsets:                             self.offsets,
+        }
+    }
 }
 
 #[derive(Clone,Eq,PartialEq,Hash,Debug,Default)]
-pub struct SecurityData {
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub struct SecurityData<'a> {
+    /// Set if the pass carries the beginning of security data, even if nothing follows.
+    pub(crate) present: bool,
     pub(crate) type_of_security_data: Option<char>,
-    pub(crate) security_data: Option<String>,
+    /// Length of the security data, as written.
+    pub(crate) length: Option<Cow<'a, str>>,
+    pub(crate) security_data: Option<Cow<'a, str>>,
 }
 
-impl SecurityData {
+impl SecurityData<'_> {
 
     /// Vendor specific flag indicating the type of the security data which follows.
-    pub fn type_of_security_data(&self) -> Option<char> {
-        self.type_of_security_data
+    pub fn type_of_security_data(&self) -> Option<SecurityDataType> {
+        self.type_of_security_data.map(SecurityDataType::from_char)
     }
 
     /// Security data used to verify the boarding pass was not tampered with.
@@ -217,23 +369,61 @@ impl SecurityData {
         self.security_data.as_deref()
     }
 
+    /// Copies the data borrowing the input, so that it outlives it.
+    pub fn into_owned(self) -> SecurityData<'static> {
+        SecurityData {
+            present:               self.present,
+            type_of_security_data: self.type_of_security_data,
+            length:                self.length.map(owned),
+            security_data:         self.security_data.map(owned),
+        }
+    }
 }
 
+/// A boarding pass as it is written, without any validation of the fields.
+///
+/// The fields borrow the input they were parsed from, until they are replaced or the
+/// pass is made independent of the input with [`Bcbp::into_owned()`]. Written back with
+/// `to_string()`, the pass reproduces the input byte for byte.
+///
+/// The setters replace a field which is already present, padding the value with
+/// spaces to the length of the field so that the rest of the pass is left untouched.
+/// Conditional fields the pass does not carry cannot be set.
+///
+/// ```
+/// use iata::bcbp::raw;
+///
+/// let src = "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100";
+/// let mut pass = raw::Bcbp::from(src).unwrap();
+/// assert_eq!(pass.to_string(), src);
+///
+/// pass.legs_mut()[0].set_seat("014C").unwrap();
+/// assert_eq!(pass.to_string(), "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L014C0052 100");
+/// ```
 #[derive(Clone,Eq,PartialEq,Hash,Debug,Default)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
 pub struct Bcbp<'a> {
-    pub(crate) pax_name: &'a str,
-    pub(crate) eticket_flag: char,
-    pub(crate) pax_description: Option<char>,
-    pub(crate) source_of_check_in: Option<char>,
-    pub(crate) source_of_boarding_pass_issuance: Option<char>,
-    pub(crate) date_of_issue_of_boarding_pass: Option<&'a str>,
-    pub(crate) doc_type: Option<char>,
-    pub(crate) airline_designator_of_boarding_pass_issuer: Option<&'a str>,
-    pub(crate) baggage_tag_license_plate_numbers: Option<&'a str>,
-    pub(crate) first_non_consecutive_baggage_tag_license_plate_numbers: Option<&'a str>,
-    pub(crate) second_non_consecutive_baggage_tag_license_plate_numbers: Option<&'a str>,
+    /// Beginning of the version number, unless the pass predates it.
+    pub(crate) version_prefix: Option<char>,
+    pub(crate) version: Option<BcbpVersion>,
+    /// Field size of the conditional unique section, as written.
+    pub(crate) unique_size: Option<Cow<'a, str>>,
+    pub(crate) pax_name: Cow<'a, str>,
+    pub(crate) eticket_flag: Cow<'a, str>,
+    pub(crate) pax_description: Option<Cow<'a, str>>,
+    pub(crate) source_of_check_in: Option<Cow<'a, str>>,
+    pub(crate) source_of_boarding_pass_issuance: Option<Cow<'a, str>>,
+    pub(crate) date_of_issue_of_boarding_pass: Option<Cow<'a, str>>,
+    pub(crate) doc_type: Option<Cow<'a, str>>,
+    pub(crate) airline_designator_of_boarding_pass_issuer: Option<Cow<'a, str>>,
+    pub(crate) baggage_tag_license_plate_numbers: Option<Cow<'a, str>>,
+    pub(crate) first_non_consecutive_baggage_tag_license_plate_numbers: Option<Cow<'a, str>>,
+    pub(crate) second_non_consecutive_baggage_tag_license_plate_numbers: Option<Cow<'a, str>>,
+    pub(crate) unexpected_data: Option<Cow<'a, str>>,
     pub(crate) legs: Vec<Leg<'a>>,
-    pub(crate) security_data: SecurityData,
+    pub(crate) security_data: SecurityData<'a>,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub(crate) offsets: Offsets,
 }
 
 impl<'a> Bcbp<'a> {
@@ -242,29 +432,42 @@ impl<'a> Bcbp<'a> {
         from_str(input)
     }
 
+    /// Version of Resolution 792 declared by the boarding pass.
+    /// Not set if the first leg carries no conditional data.
+    pub fn version(&self) -> Option<BcbpVersion> {
+        self.version
+    }
+
+    /// Data at the end of the conditional unique section which follows the fields
+    /// defined by the version of the boarding pass.
+    /// It is kept apart rather than read as fields the version does not know of.
+    pub fn unexpected_data(&self) -> Option<&str> {
+        self.unexpected_data.as_deref()
+    }
+
     /// All legs encoded into the boarding pass.
     /// At least one needs to be present to form a valid boarding pass.
-    pub fn legs(&self) -> &[Leg] {
+    pub fn legs(&self) -> &[Leg<'_>] {
         &self.legs
     }
 
     /// A reference to the optional security data used to verify a boarding pass was not tamptered with.
-    pub fn security_data(&self) -> &SecurityData {
+    pub fn security_data(&self) -> &SecurityData<'a> {
         &self.security_data
     }
 
     /// Used to differentiate between an electronic ticket ('E') and another type of travel document.
     /// Values are defined in Resolution 792.
     /// A space indicates the field is not set.
-    pub fn eticket_flag(&self) -> char {
-        self.eticket_flag
+    pub fn eticket_flag(&self) -> TicketIndicator {
+        TicketIndicator::from_char(code(&self.eticket_flag))
     }
 
     /// This describes the passenger.
     /// Values are defined in Resolution 792.
     /// Spaces indicate the field is not set.
-    pub fn pax_description(&self) -> Option<char> {
-        self.pax_description
+    pub fn pax_description(&self) -> Option<PaxType> {
+        self.pax_description.as_deref().map(code).map(PaxType::from_char)
     }
 
     /// The name of the passenger. Up to 20 characters, left-aligned, space padded.
@@ -277,18 +480,23 @@ impl<'a> Bcbp<'a> {
         &self.pax_name
     }
 
+    /// The name of the passenger split into the surname, the given names and the title.
+    pub fn passenger_name(&self) -> PassengerName {
+        PassengerName::from_bcbp(&self.pax_name)
+    }
+
     /// This field reflects channel in which the customer initiated check-in.
     /// Values are defined in Resolution 792 Attachment C.
     /// Spaces indicate the field is not set.
-    pub fn source_of_check_in(&self) -> Option<char> {
-        self.source_of_check_in
+    pub fn source_of_check_in(&self) -> Option<CheckInSource> {
+        self.source_of_check_in.as_deref().map(code).map(CheckInSource::from_char)
     }
 
     /// This field reflects channel which issued the boarding pass.
     /// Values are defined in Resolution 792.
     /// Spaces indicate the field is not set.
-    pub fn source_of_boarding_pass_issuance(&self) -> Option<char> {
-        self.source_of_boarding_pass_issuance
+    pub fn source_of_boarding_pass_issuance(&self) -> Option<BoardingPassSource> {
+        self.source_of_boarding_pass_issuance.as_deref().map(code).map(BoardingPassSource::from_char)
     }
 
     /// Optionally the 4-digit Julian date representing when the boarding pass
@@ -302,10 +510,20 @@ impl<'a> Bcbp<'a> {
         self.date_of_issue_of_boarding_pass.as_deref()
     }
 
+    /// The date of issue of the boarding pass, with the decade resolved against `for_date`.
+    /// See [`JulianIssueDate::to_date_adapt()`].
+    /// Spaces indicate the field is not set.
+    pub fn issue_date(&self, for_date: Date) -> Result<Option<Date>, DateError> {
+        match self.date_of_issue_of_boarding_pass.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(x) => x.parse::<JulianIssueDate>()?.to_date_adapt(for_date).map(Some),
+        }
+    }
+
     /// The type of the document, 'B' indicating a boarding pass.
     /// Spaces indicate the field is not set.
-    pub fn doc_type(&self) -> Option<char> {
-        self.doc_type
+    pub fn doc_type(&self) -> Option<DocumentType> {
+        self.doc_type.as_deref().map(code).map(DocumentType::from_char)
     }
 
     /// Airline code of the boarding pass issuer.
@@ -340,4 +558,60 @@ impl<'a> Bcbp<'a> {
     pub fn second_non_consecutive_baggage_tag_license_plate_numbers(&self) -> Option<&str> {
         self.second_non_consecutive_baggage_tag_license_plate_numbers.as_deref()
     }
+
+    /// All legs encoded into the boarding pass, to be modified with their setters.
+    pub fn legs_mut(&mut self) -> &mut [Leg<'a>] {
+        &mut self.legs
+    }
+
+    gen_set!(set set_pax_name for pax_name as PassengerName);
+    gen_set!(set set_eticket_flag for eticket_flag as ElectronicTicketIndicator);
+    gen_set!(set set_pax_description for pax_description as conditional PassengerDescription);
+    gen_set!(set set_source_of_check_in for source_of_check_in as conditional SourceOfCheckIn);
+    gen_set!(set set_source_of_boarding_pass_issuance for source_of_boarding_pass_issuance as conditional SourceOfBoardingPassIssuance);
+    gen_set!(set set_date_of_issue_of_boarding_pass for date_of_issue_of_boarding_pass as conditional DateOfIssueOfBoardingPass);
+    gen_set!(set set_doc_type for doc_type as conditional DocumentType);
+    gen_set!(set set_airline_designator_of_boarding_pass_issuer for airline_designator_of_boarding_pass_issuer as conditional AirlineDesignatorOfBoardingPassIssuer);
+    gen_set!(set set_baggage_tag_license_plate_numbers for baggage_tag_license_plate_numbers as conditional BaggageTagLicensePlateNumbers);
+    gen_set!(set set_first_non_consecutive_baggage_tag_license_plate_numbers for first_non_consecutive_baggage_tag_license_plate_numbers as conditional FirstNonConsecutiveBaggageTagLicensePlateNumbers);
+    gen_set!(set set_second_non_consecutive_baggage_tag_license_plate_numbers for second_non_consecutive_baggage_tag_license_plate_numbers as conditional SecondNonConsecutiveBaggageTagLicensePlateNumbers);
+
+    /// Copies the fields borrowing the input, so that the pass outlives it.
+    ///
+    /// ```
+    /// use iata::bcbp::raw;
+    ///
+    /// let pass = {
+    ///     let src = String::from("M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100");
+    ///     raw::Bcbp::from(&src).unwrap().into_owned()
+    /// };
+    ///
+    /// assert_eq!(pass.legs()[0].seat(), "013A");
+    /// ```
+    pub fn into_owned(self) -> Bcbp<'static> {
+        Bcbp {
+            version_prefix:                   self.version_prefix,
+            version:                          self.version,
+            unique_size:                      self.unique_size.map(owned),
+            pax_name:                         owned(self.pax_name),
+            eticket_flag:                     owned(self.eticket_flag),
+            pax_description:                  self.pax_description.map(owned),
+            source_of_check_in:               self.source_of_check_in.map(owned),
+            source_of_boarding_pass_issuance: self.source_of_boarding_pass_issuance.map(owned),
+            date_of_issue_of_boarding_pass:   self.date_of_issue_of_boarding_pass.map(owned),
+            doc_type:                         self.doc_type.map(owned),
+            airline_designator_of_boarding_pass_issuer:
+                self.airline_designator_of_boarding_pass_issuer.map(owned),
+            baggage_tag_license_plate_numbers:
+                self.baggage_tag_license_plate_numbers.map(owned),
+            first_non_consecutive_baggage_tag_license_plate_numbers:
+                self.first_non_consecutive_baggage_tag_license_plate_numbers.map(owned),
+            second_non_consecutive_baggage_tag_license_plate_numbers:
+                self.second_non_consecutive_baggage_tag_license_plate_numbers.map(owned),
+            unexpected_data:                  self.unexpected_data.map(owned),
+            legs:                             self.legs.into_iter().map(Leg::into_owned).collect(),
+            security_data:                    self.security_data.into_owned(),
+            offsets:                          self.offsets,
+        }
+    }
 }
@@ -3,189 +3,333 @@
 // This software may be modified and distributed under the terms
 // of the MIT license.  See the LICENSE file for details.
 
+use std::borrow::Cow;
+
 use crate::bcbp::{
     field::Field,
     chunk::Chunk,
-    error::{Error, BcbpResult},
-    raw::{Bcbp, Leg, SecurityData},
+    version::BcbpVersion,
+    diagnostic::Severity,
+    error::{Error, ErrorKind, BcbpResult},
+    raw::{Bcbp, Leg, SecurityData, Offsets},
 };
 
 /// Parses a boarding pass from `input_data` representable as a string reference.
-pub fn from_str<'a>(input: &'_ str) -> BcbpResult<Bcbp<'_>> {
+pub fn from_str(input: &'_ str) -> BcbpResult<Bcbp<'_>> {
 
-    if !input.is_ascii() {
-        return Err(Error::InvalidCharacters);
+    if let Some(offset) = input.bytes().position(|x| !x.is_ascii()) {
+        return Err(Error::from(ErrorKind::InvalidCharacters).with_offset(offset));
     }
 
-    let mut chunk = Chunk::new(input);
+    let mut bcbp = Bcbp::default();
+    parse(&mut bcbp, Chunk::new(input))?;
+
+    Ok(bcbp)
+}
+
+/// Parses a boarding pass into `bcbp`, which holds whatever was parsed in case of failure.
+///
+/// This is the only implementation of the grammar, the owned `Bcbp` validates the
+/// fields scanned here. How strictly the input is checked, and whether problems are
+/// collected rather than failing, depends on the `chunk`.
+pub(crate) fn parse<'a>(bcbp: &mut Bcbp<'a>, mut chunk: Chunk<'a>) -> BcbpResult<()> {
 
+    let start = chunk.offset();
     let code = chunk.fetch_char(Field::FormatCode)?;
 
     if code != 'M' {
-        return Err(Error::InvalidFormatCode(code))
+        let range = start..chunk.offset();
+        chunk.report(Field::FormatCode, range, ErrorKind::InvalidFormatCode(code).into(), Severity::Error)?;
     }
 
     // The number of legs informs the breakdown of the various field iterators.
-    let leg_count = chunk.fetch_usize(Field::NumberOfLegsEncoded, 10)?;
+    let start = chunk.offset();
+    let legs = match chunk.fetch_usize(Field::NumberOfLegsEncoded, 10) {
+        Ok(legs) if (1..=9).contains(&legs) => legs,
+        Ok(_) => {
+            let range = start..chunk.offset();
+            chunk.report(Field::NumberOfLegsEncoded, range, ErrorKind::InvalidLegsCount.into(), Severity::Error)?;
+            1
+        },
+        // Already collected, the rest is read as a single leg.
+        Err(error) if error.kind() == ErrorKind::ExpectedInteger && chunk.collects() => 1,
+        Err(error) => return Err(error),
+    };
 
-    // Create a parser for the mandatory unique fields.
-    let mut bcbp = Bcbp::default();
+    bcbp.pax_name = fetch(&mut chunk, &mut bcbp.offsets, Field::PassengerName)?;
+    bcbp.eticket_flag = fetch(&mut chunk, &mut bcbp.offsets, Field::ElectronicTicketIndicator)?;
 
-    bcbp.pax_name = chunk.fetch_str(Field::PassengerName)?;
-    bcbp.eticket_flag =
-        chunk.fetch_char(Field::ElectronicTicketIndicator)?;
+    // Set when the pass predates the version number, see `BcbpVersion::Legacy`.
+    let mut legacy = false;
 
-    for leg_index in 0..leg_count {
+    for leg_index in 0..legs {
         let mut leg = Leg::default();
 
-        // Mandatory fields common to all legs.
-        leg.pnr = chunk.fetch_str(Field::OperatingCarrierPnrCode)?;
-        leg.src_airport = chunk.fetch_str(Field::FromCityAirportCode)?;
-        leg.dst_airport = chunk.fetch_str(Field::ToCityAirportCode)?;
-        leg.airline     = chunk.fetch_str(Field::OperatingCarrierDesignator)?;
-        leg.flight_number = chunk.fetch_str(Field::FlightNumber)?;
-        leg.flight_day    = chunk.fetch_str(Field::DateOfFlight)?;
-        leg.compartment   = chunk.fetch_char(Field::CompartmentCode)?;
-        leg.seat          = chunk.fetch_str(Field::SeatNumber)?;
-        leg.checkin_sequence = chunk
-            .fetch_str(Field::CheckInSequenceNumber)?;
-        leg.pax_status = chunk.fetch_char(Field::PassengerStatus)?;
-
-        // Field size of the variable size field that follows for the leg.
-        let conditional_item_size =
-            chunk.fetch_usize(Field::FieldSizeOfVariableSizeField, 16)?;
-        if conditional_item_size > 0 {
-            // chunk over the entire set of conditional fields.
-            let mut conditional_item_chunk = chunk.fetch_chunk(conditional_item_size)?;
-
-            // The first leg may contain some optional fields at the root level.
-            if leg_index == 0 {
-                // Validate the beginning of version number tag as a sanity check.
-                let prefix = conditional_item_chunk.fetch_char(Field::BeginningOfVersionNumber)?;
-                if prefix != '<' && prefix != '>' {
-                    return Err(Error::InvalidPrefix(Field::BeginningOfVersionNumber, prefix))
-                }
-
-                // The version number is part of the structure and must be consumed, but is not used.
-                if conditional_item_chunk.len() > 0 {
-                    let _ = conditional_item_chunk.fetch_str(Field::VersionNumber)?;
-                }
-
-                // Conditional unique fields are embedded in their own variable-length wrapper.
-                if conditional_item_chunk.len() > 0 {
-                    let len = conditional_item_chunk
-                        .fetch_usize(Field::FieldSizeOfStructuredMessageUnique, 16)?;
-                    if len > 0 {
-                        let mut unique_chunk = conditional_item_chunk.fetch_chunk(len)?;
-
-                        bcbp.pax_description =
-                            unique_chunk.fetch_char_opt(Field::PassengerDescription)?;
-                        bcbp.source_of_check_in =
-                            unique_chunk.fetch_char_opt(Field::SourceOfCheckIn)?;
-                        bcbp.source_of_boarding_pass_issuance = unique_chunk
-                            .fetch_char_opt(Field::SourceOfBoardingPassIssuance)?;
-                        bcbp.date_of_issue_of_boarding_pass = unique_chunk
-                            .fetch_str_opt(Field::DateOfIssueOfBoardingPass)?
-                            .map(Into::into);
-                        bcbp.doc_type =
-                            unique_chunk.fetch_char_opt(Field::DocumentType)?;
-                        bcbp.airline_designator_of_boarding_pass_issuer = unique_chunk
-                            .fetch_str_opt(Field::AirlineDesignatorOfBoardingPassIssuer)?
-                            .map(Into::into);
-                        bcbp.baggage_tag_license_plate_numbers = unique_chunk
-                            .fetch_str_opt(Field::BaggageTagLicensePlateNumbers)?
-                            .map(Into::into);
-                        bcbp.first_non_consecutive_baggage_tag_license_plate_numbers =
-                            unique_chunk
-                                .fetch_str_opt(
-                                    Field::FirstNonConsecutiveBaggageTagLicensePlateNumbers,
-                                )?
-                                .map(Into::into);
-                        bcbp.second_non_consecutive_baggage_tag_license_plate_numbers =
-                            unique_chunk
-                                .fetch_str_opt(
-                                    Field::SecondNonConsecutiveBaggageTagLicensePlateNumbers,
-                                )?
-                                .map(Into::into);
-                    }
-                }
-            }
-
-            // Conditional fields common to all legs.
-            if conditional_item_chunk.len() > 0 {
-                let len = conditional_item_chunk
-                    .fetch_usize(Field::FieldSizeOfStructuredMessageRepeated, 16)?;
-                if len > 0 {
-                    let mut repeated_chunk = conditional_item_chunk.fetch_chunk(len)?;
-
-                    leg.airline_numeric_code = repeated_chunk
-                        .fetch_str_opt(Field::AirlineNumericCode)?
-                        .map(Into::into);
-                    leg.document_form_serial_number = repeated_chunk
-                        .fetch_str_opt(Field::DocumentFormSerialNumber)?
-                        .map(Into::into);
-                    leg.selectee_indicator =
-                        repeated_chunk.fetch_char_opt(Field::SelecteeIndicator)?;
-                    leg.international_document_verification = repeated_chunk
-                        .fetch_char_opt(Field::InternationalDocumentVerification)?;
-                    leg.marketing_carrier_designator = repeated_chunk
-                        .fetch_str_opt(Field::MarketingCarrierDesignator)?
-                        .map(Into::into);
-                    leg.frequent_flyer_airline = repeated_chunk
-                        .fetch_str_opt(Field::FrequentFlyerAirlineDesignator)?
-                        .map(Into::into);
-                    leg.frequent_flyer_number = repeated_chunk
-                        .fetch_str_opt(Field::FrequentFlyerNumber)?
-                        .map(Into::into);
-                    leg.id_ad_indicator =
-                        repeated_chunk.fetch_char_opt(Field::IdAdIndicator)?;
-                    leg.free_baggage_allowance = repeated_chunk
-                        .fetch_str_opt(Field::FreeBaggageAllowance)?
-                        .map(Into::into);
-                    leg.fast_track = repeated_chunk.fetch_char_opt(Field::FastTrack)?;
-                }
-            }
-
-            // Any remaining text is ascribed to airline use.
-            if conditional_item_chunk.len() > 0 {
-                let len = conditional_item_chunk.len();
-                let body = conditional_item_chunk.fetch_str_len(Field::AirlineIndividualUse, len)?;
-                leg.airline_individual_use = Some(body);
-            }
-        }
+        chunk.set_leg(Some(leg_index));
+        let result = parse_leg(bcbp, &mut leg, &mut chunk, leg_index, &mut legacy);
+        chunk.set_leg(None);
 
         bcbp.legs.push(leg);
+        result?;
     }
 
     // Remaining input is ascribed to Security Data.
     if chunk.len() > 0 {
 
+        let start = chunk.offset();
         let prefix = chunk.fetch_char(Field::BeginningOfSecurityData)?;
         if prefix != '^' {
-            return Err(Error::InvalidPrefix(Field::BeginningOfSecurityData, prefix))
+            let range = start..chunk.offset();
+            let error = ErrorKind::InvalidPrefix(prefix).into();
+            chunk.report(Field::BeginningOfSecurityData, range, error, Severity::Error)?;
         }
 
-        let mut security_data = SecurityData::default();
-
         // The security data type captured as a separate field set as the next field, data length, is discarded.
-        security_data.type_of_security_data =
-            chunk.fetch_char_opt(Field::TypeOfSecurityData)?;
+        bcbp.security_data = SecurityData {
+            present: true,
+            type_of_security_data: chunk.fetch_char_opt(Field::TypeOfSecurityData)?,
+            ..Default::default()
+        };
 
         // Scan the length of the security data.
         if chunk.len() > 0 {
-            let len = chunk.fetch_usize(Field::LengthOfSecurityData, 16)?;
+            let (len, size) = fetch_size(&mut chunk, Field::LengthOfSecurityData)?;
+            bcbp.security_data.length = Some(size.into());
             if len > 0 {
-                let body = chunk.fetch_str_len(Field::SecurityData, len as usize)?;
-                security_data.security_data = Some(body.into());
+                let body = chunk.fetch_str_len(Field::SecurityData, len)?;
+                bcbp.security_data.security_data = Some(body.into());
             }
         }
-
-        bcbp.security_data = security_data;
     }
 
     if !chunk.eof() {
-        Err(Error::TrailingData)
+        let range = chunk.offset()..chunk.offset() + chunk.len();
+        Err(chunk.fail(Field::SecurityData, range, ErrorKind::TrailingData.into()))
     } else {
-        Ok(bcbp)
+        Ok(())
     }
 }
+
+/// Parses the leg at `leg_index` into `leg`. The first leg also carries the
+/// version and the unique conditional fields, which are parsed into `bcbp`.
+fn parse_leg<'a>(
+    bcbp: &mut Bcbp<'a>,
+    leg: &mut Leg<'a>,
+    chunk: &mut Chunk<'a>,
+    leg_index: usize,
+    legacy: &mut bool,
+) -> BcbpResult<()> {
+
+    // Mandatory fields common to all legs.
+    leg.pnr              = fetch(chunk, &mut leg.offsets, Field::OperatingCarrierPnrCode)?;
+    leg.src_airport      = fetch(chunk, &mut leg.offsets, Field::FromCityAirportCode)?;
+    leg.dst_airport      = fetch(chunk, &mut leg.offsets, Field::ToCityAirportCode)?;
+    leg.airline          = fetch(chunk, &mut leg.offsets, Field::OperatingCarrierDesignator)?;
+    leg.flight_number    = fetch(chunk, &mut leg.offsets, Field::FlightNumber)?;
+    leg.flight_day       = fetch(chunk, &mut leg.offsets, Field::DateOfFlight)?;
+    leg.compartment      = fetch(chunk, &mut leg.offsets, Field::CompartmentCode)?;
+    leg.seat             = fetch(chunk, &mut leg.offsets, Field::SeatNumber)?;
+    leg.checkin_sequence = fetch(chunk, &mut leg.offsets, Field::CheckInSequenceNumber)?;
+    leg.pax_status       = fetch(chunk, &mut leg.offsets, Field::PassengerStatus)?;
+
+    // Field size of the variable size field that follows for the leg.
+    let start = chunk.offset();
+    let (mut conditional_size, size) = fetch_size(chunk, Field::FieldSizeOfVariableSizeField)?;
+    leg.conditional_size = size.into();
+
+    if conditional_size > chunk.len() {
+        let range = start..chunk.offset();
+        chunk.report(Field::FieldSizeOfVariableSizeField, range, ErrorKind::SubsectionTooLong.into(), Severity::Error)?;
+        conditional_size = chunk.len();
+    }
+
+    if conditional_size == 0 {
+        return Ok(())
+    }
+
+    // chunk over the entire set of conditional fields.
+    let mut conditional_item =
+        chunk.fetch_chunk(Field::FieldSizeOfVariableSizeField, conditional_size)?;
+
+    // Passes issued before the version number was introduced start their
+    // unstructured data right away rather than with a version marker.
+    if leg_index == 0 && is_legacy(conditional_item.rest()) {
+        *legacy = true;
+        bcbp.version = Some(BcbpVersion::Legacy);
+    }
+
+    // The first leg may contain some optional fields at the root level.
+    if !*legacy && leg_index == 0 {
+        conditional_item.set_leg(None);
+
+        // Validate the beginning of version number tag as a sanity check.
+        let start = conditional_item.offset();
+        let prefix = conditional_item.fetch_char(Field::BeginningOfVersionNumber)?;
+        if prefix != '<' && prefix != '>' {
+            let range = start..conditional_item.offset();
+            let error = ErrorKind::InvalidPrefix(prefix).into();
+            conditional_item.report(Field::BeginningOfVersionNumber, range, error, Severity::Error)?;
+        }
+        bcbp.version_prefix = Some(prefix);
+
+        bcbp.version = Some(BcbpVersion::from_char(
+            conditional_item.fetch_char(Field::VersionNumber)?
+        ));
+
+        // Conditional unique fields are embedded in their own variable-length wrapper.
+        if conditional_item.len() > 0 {
+            let (len, size) = fetch_size(&mut conditional_item, Field::FieldSizeOfStructuredMessageUnique)?;
+            bcbp.unique_size = Some(size.into());
+            if len > 0 {
+                let mut section_chunk = conditional_item
+                    .fetch_chunk(Field::FieldSizeOfStructuredMessageUnique, len)?;
+
+                // Only the fields defined by the declared version are read.
+                let schema = bcbp.version.unwrap_or(BcbpVersion::LATEST);
+                let mut unique_chunk = section_chunk.fetch_chunk_upto(schema.unique_len());
+                let offsets = &mut bcbp.offsets;
+
+                bcbp.pax_description =
+                    fetch_opt(&mut unique_chunk, offsets, Field::PassengerDescription)?;
+                bcbp.source_of_check_in =
+                    fetch_opt(&mut unique_chunk, offsets, Field::SourceOfCheckIn)?;
+                bcbp.source_of_boarding_pass_issuance =
+                    fetch_opt(&mut unique_chunk, offsets, Field::SourceOfBoardingPassIssuance)?;
+                bcbp.date_of_issue_of_boarding_pass =
+                    fetch_opt(&mut unique_chunk, offsets, Field::DateOfIssueOfBoardingPass)?;
+                bcbp.doc_type =
+                    fetch_opt(&mut unique_chunk, offsets, Field::DocumentType)?;
+                bcbp.airline_designator_of_boarding_pass_issuer =
+                    fetch_opt(&mut unique_chunk, offsets, Field::AirlineDesignatorOfBoardingPassIssuer)?;
+                bcbp.baggage_tag_license_plate_numbers =
+                    fetch_opt(&mut unique_chunk, offsets, Field::BaggageTagLicensePlateNumbers)?;
+                bcbp.first_non_consecutive_baggage_tag_license_plate_numbers =
+                    fetch_opt(&mut unique_chunk, offsets, Field::FirstNonConsecutiveBaggageTagLicensePlateNumbers)?;
+                bcbp.second_non_consecutive_baggage_tag_license_plate_numbers =
+                    fetch_opt(&mut unique_chunk, offsets, Field::SecondNonConsecutiveBaggageTagLicensePlateNumbers)?;
+
+                let start = section_chunk.offset();
+                bcbp.unexpected_data = section_chunk.fetch_rest().map(Cow::from);
+
+                if bcbp.unexpected_data.is_some() {
+                    let range = start..section_chunk.offset();
+                    let field = Field::FieldSizeOfStructuredMessageUnique;
+                    section_chunk.report(field, range, ErrorKind::CoditionalDataSize.into(), Severity::Warning)?;
+                }
+            }
+        }
+
+        conditional_item.set_leg(Some(leg_index));
+    }
+
+    // Conditional fields common to all legs.
+    if !*legacy && conditional_item.len() > 0 {
+        let (len, size) = fetch_size(&mut conditional_item, Field::FieldSizeOfStructuredMessageRepeated)?;
+        leg.repeated_size = Some(size.into());
+        if len > 0 {
+            let mut section_chunk = conditional_item
+                .fetch_chunk(Field::FieldSizeOfStructuredMessageRepeated, len)?;
+
+            // Only the fields defined by the declared version are read.
+            let schema = bcbp.version.unwrap_or(BcbpVersion::LATEST);
+            let mut repeated_chunk = section_chunk.fetch_chunk_upto(schema.repeated_len());
+            let offsets = &mut leg.offsets;
+
+            leg.airline_numeric_code =
+                fetch_opt(&mut repeated_chunk, offsets, Field::AirlineNumericCode)?;
+            leg.document_form_serial_number =
+                fetch_opt(&mut repeated_chunk, offsets, Field::DocumentFormSerialNumber)?;
+            leg.selectee_indicator =
+                fetch_opt(&mut repeated_chunk, offsets, Field::SelecteeIndicator)?;
+            leg.international_document_verification =
+                fetch_opt(&mut repeated_chunk, offsets, Field::InternationalDocumentVerification)?;
+            leg.marketing_carrier_designator =
+                fetch_opt(&mut repeated_chunk, offsets, Field::MarketingCarrierDesignator)?;
+            leg.frequent_flyer_airline =
+                fetch_opt(&mut repeated_chunk, offsets, Field::FrequentFlyerAirlineDesignator)?;
+            leg.frequent_flyer_number =
+                fetch_opt(&mut repeated_chunk, offsets, Field::FrequentFlyerNumber)?;
+            leg.id_ad_indicator =
+                fetch_opt(&mut repeated_chunk, offsets, Field::IdAdIndicator)?;
+            leg.free_baggage_allowance =
+                fetch_opt(&mut repeated_chunk, offsets, Field::FreeBaggageAllowance)?;
+            leg.fast_track =
+                fetch_opt(&mut repeated_chunk, offsets, Field::FastTrack)?;
+
+            let start = section_chunk.offset();
+            leg.unexpected_data = section_chunk.fetch_rest().map(Cow::from);
+
+            if leg.unexpected_data.is_some() {
+                let range = start..section_chunk.offset();
+                let field = Field::FieldSizeOfStructuredMessageRepeated;
+                section_chunk.report(field, range, ErrorKind::CoditionalDataSize.into(), Severity::Warning)?;
+            }
+        }
+    }
+
+    // Any remaining text is ascribed to airline use.
+    if conditional_item.len() > 0 {
+        let len = conditional_item.len();
+        let body = conditional_item.fetch_str_len(Field::AirlineIndividualUse, len)?;
+        leg.airline_individual_use = Some(body.into());
+    }
+
+    Ok(())
+}
+
+/// Scans the fixed-length `field`, recording its offset in `offsets`.
+fn fetch<'a>(chunk: &mut Chunk<'a>, offsets: &mut Offsets, field: Field) -> BcbpResult<Cow<'a, str>> {
+    let offset = chunk.offset();
+    let value = chunk.fetch_str(field)?;
+    offsets.insert(field, offset);
+
+    Ok(value.into())
+}
+
+/// Scans the fixed-length `field` if there is more input to process, recording its offset in `offsets`.
+fn fetch_opt<'a>(chunk: &mut Chunk<'a>, offsets: &mut Offsets, field: Field) -> BcbpResult<Option<Cow<'a, str>>> {
+    let offset = chunk.offset();
+    let value = chunk.fetch_str_opt(field)?;
+    if value.is_some() {
+        offsets.insert(field, offset);
+    }
+
+    Ok(value.map(Cow::from))
+}
+
+/// Checks if the variable size field of the first leg is laid out as in the passes issued
+/// before the version number was introduced: data of the airline starting with a letter or
+/// a digit, which cannot be read as a version number followed by the sizes of the sections
+/// it holds. Anything else starts with a version marker, whether valid or not.
+fn is_legacy(section: &str) -> bool {
+    match section.as_bytes().split_first() {
+        Some((first, rest)) => first.is_ascii_alphanumeric() && !is_versioned(rest),
+        None                => false,
+    }
+}
+
+/// Checks if `data` reads as a version number, followed by the size of the unique section
+/// and the size of the repeated one if any data is left, both sections fitting in `data`.
+fn is_versioned(data: &[u8]) -> bool {
+    fn sized(data: &[u8]) -> Option<&[u8]> {
+        let size = data.get(..2).filter(|x| x.iter().all(u8::is_ascii_hexdigit))?;
+        let len = usize::from_str_radix(std::str::from_utf8(size).ok()?, 16).ok()?;
+
+        data.get(2 + len..)
+    }
+
+    match data.split_first() {
+        Some((version, rest)) if version.is_ascii_digit() => {
+            rest.is_empty() || sized(rest).is_some_and(|rest| rest.is_empty() || sized(rest).is_some())
+        }
+        _ => false,
+    }
+}
+
+/// Scans a size field encoded in hexadecimal, returning its value and the field as written.
+fn fetch_size<'a>(chunk: &mut Chunk<'a>, field: Field) -> BcbpResult<(usize, &'a str)> {
+    let rest = chunk.rest();
+    let len = chunk.fetch_usize(field, 16)?;
+
+    Ok((len, &rest[..field.len()]))
+}
@@ -0,0 +1,125 @@
+// Copyright (C) 2018 Martin Mroz
+//
+// This software may be modified and distributed under the terms
+// of the MIT license.  See the LICENSE file for details.
+
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::bcbp::raw::{Bcbp, Leg};
+
+/// Writes the pass back in the form it was parsed from, sizes and padding included.
+impl fmt::Display for Bcbp<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+
+        write!(f, "{}", SignedData(self))?;
+
+        let security = &self.security_data;
+
+        if security.present {
+            f.write_char('^')?;
+
+            if let Some(kind) = security.type_of_security_data {
+                f.write_char(kind)?;
+            }
+
+            write_optional(f, &[&security.length, &security.security_data])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The part of a pass which precedes the beginning of the security data,
+/// over which the security data is computed.
+pub(crate) struct SignedData<'p, 'a>(pub(crate) &'p Bcbp<'a>);
+
+impl fmt::Display for SignedData<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pass = self.0;
+
+        write!(f, "M{}{}{}", pass.legs.len(), pass.pax_name, pass.eticket_flag)?;
+
+        for (leg_index, leg) in pass.legs.iter().enumerate() {
+            write_mandatory(f, leg)?;
+            f.write_str(&leg.conditional_size)?;
+
+            // The first leg carries the version and the unique conditional fields.
+            if leg_index == 0 {
+                if let Some(prefix) = pass.version_prefix {
+                    f.write_char(prefix)?;
+
+                    if let Some(version) = pass.version {
+                        f.write_char(version.to_char())?;
+                    }
+                }
+
+                if let Some(ref size) = pass.unique_size {
+                    f.write_str(size)?;
+                    write_optional(f, &[
+                        &pass.pax_description,
+                        &pass.source_of_check_in,
+                        &pass.source_of_boarding_pass_issuance,
+                        &pass.date_of_issue_of_boarding_pass,
+                        &pass.doc_type,
+                        &pass.airline_designator_of_boarding_pass_issuer,
+                        &pass.baggage_tag_license_plate_numbers,
+                        &pass.first_non_consecutive_baggage_tag_license_plate_numbers,
+                        &pass.second_non_consecutive_baggage_tag_license_plate_numbers,
+                        &pass.unexpected_data,
+                    ])?;
+                }
+            }
+
+            if let Some(ref size) = leg.repeated_size {
+                f.write_str(size)?;
+                write_optional(f, &[
+                    &leg.airline_numeric_code,
+                    &leg.document_form_serial_number,
+                    &leg.selectee_indicator,
+                    &leg.international_document_verification,
+                    &leg.marketing_carrier_designator,
+                    &leg.frequent_flyer_airline,
+                    &leg.frequent_flyer_number,
+                    &leg.id_ad_indicator,
+                    &leg.free_baggage_allowance,
+                    &leg.fast_track,
+                    &leg.unexpected_data,
+                ])?;
+            }
+
+            write_optional(f, &[&leg.airline_individual_use])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the mandatory fields of the `leg` which precede the variable size field.
+fn write_mandatory(f: &mut fmt::Formatter, leg: &Leg) -> fmt::Result {
+    for field in [
+        &leg.pnr,
+        &leg.src_airport,
+        &leg.dst_airport,
+        &leg.airline,
+        &leg.flight_number,
+        &leg.flight_day,
+        &leg.compartment,
+        &leg.seat,
+        &leg.checkin_sequence,
+        &leg.pax_status,
+    ] {
+        f.write_str(field)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the `fields` present in the pass, in order.
+fn write_optional(f: &mut fmt::Formatter, fields: &[&Option<Cow<str>>]) -> fmt::Result {
+    for field in fields.iter().copied().flatten() {
+        f.write_str(field)?;
+    }
+
+    Ok(())
+}
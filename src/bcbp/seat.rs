@@ -0,0 +1,151 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::{Error, ErrorKind, BcbpResult};
+
+/// The seat of a passenger, as encoded in item 25.
+///
+/// Seats of the cabin are ordered by row, then by letter. They come before the
+/// special values, which are ordered as declared.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "SeatRepr"))]
+pub enum Seat {
+    /// A seat of the cabin, such as `012C`.
+    Assigned { row: u16, letter: char },
+    /// An infant without a seat of its own, `INF`.
+    Infant,
+    /// The seat is assigned at the gate, `GATE`.
+    Gate,
+    /// A standby passenger, `STBY`.
+    Standby,
+    /// No seat, `NS`.
+    NoSeat,
+    /// Any other value up to 4 characters long, defined by the host system.
+    Other(String),
+}
+
+/// The variants of a [`Seat`] as they are deserialized, before validation.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+enum SeatRepr {
+    Assigned { row: u16, letter: char },
+    Infant,
+    Gate,
+    Standby,
+    NoSeat,
+    Other(String),
+}
+
+/// Validates a seat of the cabin as [`Seat::new()`] does, and any other value as it is parsed.
+#[cfg(feature = "with-serde")]
+impl TryFrom<SeatRepr> for Seat {
+    type Error = Error;
+
+    fn try_from(repr: SeatRepr) -> Result<Self, Self::Error> {
+        match repr {
+            SeatRepr::Assigned { row, letter } => Seat::new(row, letter),
+            SeatRepr::Infant                   => Ok(Seat::Infant),
+            SeatRepr::Gate                     => Ok(Seat::Gate),
+            SeatRepr::Standby                  => Ok(Seat::Standby),
+            SeatRepr::NoSeat                   => Ok(Seat::NoSeat),
+            SeatRepr::Other(s)                 => s.parse(),
+        }
+    }
+}
+
+impl Seat {
+
+    /// Constructs a seat of the cabin.
+    ///
+    /// # Errors
+    /// * [`ErrorKind::MandatoryDataSize`] if `row` is not between 1 and 999.
+    /// * [`ErrorKind::AlphaExpected`] if `letter` is not an uppercase letter.
+    pub fn new(row: u16, letter: char) -> BcbpResult<Self> {
+
+        if !(1..=999).contains(&row) {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if !letter.is_ascii_uppercase() {
+            return Err(ErrorKind::AlphaExpected.into())
+        }
+
+        Ok(Seat::Assigned { row, letter })
+    }
+
+    /// The row of a seat of the cabin.
+    pub fn row(&self) -> Option<u16> {
+        match *self {
+            Seat::Assigned { row, .. } => Some(row),
+            _                          => None,
+        }
+    }
+
+    /// The letter of a seat of the cabin.
+    pub fn letter(&self) -> Option<char> {
+        match *self {
+            Seat::Assigned { letter, .. } => Some(letter),
+            _                             => None,
+        }
+    }
+}
+
+/// Writes the 4-character encoding, the row padded with zeroes and the special values with spaces.
+///
+/// ```
+/// use iata::bcbp::Seat;
+///
+/// assert_eq!(Seat::new(12, 'C').unwrap().to_string(), "012C");
+/// assert_eq!(Seat::Infant.to_string(), "INF ");
+/// ```
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Seat::Assigned { row, letter } => write!(f, "{:03}{}", row, letter),
+            Seat::Infant                   => write!(f, "INF "),
+            Seat::Gate                     => write!(f, "GATE"),
+            Seat::Standby                  => write!(f, "STBY"),
+            Seat::NoSeat                   => write!(f, "NS  "),
+            Seat::Other(s)                 => write!(f, "{:<4}", s),
+        }
+    }
+}
+
+/// Parses a seat, with or without the leading zeroes of the row and the trailing spaces.
+impl FromStr for Seat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_end();
+
+        if s.is_empty() || s.len() > 4 {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if !s.bytes().all(|x| x.is_ascii_graphic() || x == b' ') {
+            return Err(ErrorKind::InvalidCharacters.into())
+        }
+
+        match s {
+            "INF"  => return Ok(Seat::Infant),
+            "GATE" => return Ok(Seat::Gate),
+            "STBY" => return Ok(Seat::Standby),
+            "NS"   => return Ok(Seat::NoSeat),
+            _      => {}
+        }
+
+        // The string is ASCII, so the last character is a single byte.
+        let (row, letter) = s.split_at(s.len() - 1);
+        let letter = letter.as_bytes()[0] as char;
+
+        if !row.is_empty() && row.bytes().all(|x| x.is_ascii_digit()) {
+            // Up to three digits, which always fit.
+            if let Ok(seat) = Seat::new(row.parse().unwrap(), letter) {
+                return Ok(seat)
+            }
+        }
+
+        Ok(Seat::Other(s.to_owned()))
+    }
+}
@@ -0,0 +1,230 @@
+//! Verification of the digital signature a boarding pass carries in its security data.
+//!
+//! Resolution 792 leaves the algorithm to the airline issuing the pass, which registers
+//! the public key of each type of security data it uses. The signature is computed over
+//! the pass as written, up to the beginning of the security data, and encoded in Base64.
+//! Keys on the NIST P-256 curve are checked as ECDSA signatures, DER encoded or as the
+//! 64 bytes of `r` and `s`, and RSA keys as PKCS #1 v1.5 signatures, both with SHA-256.
+//!
+//! ```
+//! use iata::bcbp::raw;
+//! use iata::bcbp::security::{KeyRegistry, Verification};
+//!
+//! let pass = raw::Bcbp::from("M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100").unwrap();
+//!
+//! assert_eq!(KeyRegistry::new().verify(&pass), Verification::Unsigned);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use p256::ecdsa;
+use rsa::RsaPublicKey;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs1v15;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::signature::Verifier;
+use sha2::Sha256;
+
+use super::{raw, AirlineDesignator, SecurityDataType};
+
+/// The error type used when loading a public key.
+#[derive(Debug, thiserror::Error)]
+pub enum KeyError {
+    #[error("KEY_READ: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("UNSUPPORTED_KEY")]
+    UnsupportedKey,
+}
+
+/// The outcome of the verification of a pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub enum Verification {
+    /// The signature matches the pass and the key of its issuer.
+    Valid,
+    /// The signature does not match, or cannot be decoded.
+    Invalid,
+    /// No key is registered for the issuer of the pass and the type of its security data.
+    UnknownKey,
+    /// The pass carries no security data.
+    Unsigned,
+}
+
+impl Verification {
+    /// Returns `true` if the signature of the pass is valid.
+    pub fn is_valid(&self) -> bool {
+        *self == Verification::Valid
+    }
+}
+
+/// The data of the `pass` its security data is computed over, which is everything
+/// preceding the beginning of the security data, as written.
+///
+/// ```
+/// use iata::bcbp::{raw, security};
+///
+/// let pass = raw::Bcbp::from("M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100^100").unwrap();
+///
+/// assert_eq!(security::signed_payload(&pass), "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100");
+/// ```
+pub fn signed_payload(pass: &raw::Bcbp) -> String {
+    raw::SignedData(pass).to_string()
+}
+
+/// A public key the security data of passes is verified with.
+#[derive(Clone)]
+pub struct PublicKey(Key);
+
+#[derive(Clone)]
+enum Key {
+    EcdsaP256(ecdsa::VerifyingKey),
+    Rsa(pkcs1v15::VerifyingKey<Sha256>),
+}
+
+impl PublicKey {
+
+    /// Reads a PEM encoded key: a `PUBLIC KEY` on the P-256 curve or of RSA,
+    /// or an `RSA PUBLIC KEY`.
+    pub fn from_pem(src: &str) -> Result<Self, KeyError> {
+        if let Ok(key) = ecdsa::VerifyingKey::from_public_key_pem(src) {
+            return Ok(Self(Key::EcdsaP256(key)))
+        }
+
+        RsaPublicKey::from_public_key_pem(src)
+            .or_else(|_| RsaPublicKey::from_pkcs1_pem(src))
+            .map(Self::rsa)
+            .map_err(|_| KeyError::UnsupportedKey)
+    }
+
+    /// Reads a DER encoded key, in the same forms as [`Self::from_pem()`].
+    pub fn from_der(src: &[u8]) -> Result<Self, KeyError> {
+        if let Ok(key) = ecdsa::VerifyingKey::from_public_key_der(src) {
+            return Ok(Self(Key::EcdsaP256(key)))
+        }
+
+        RsaPublicKey::from_public_key_der(src)
+            .or_else(|_| RsaPublicKey::from_pkcs1_der(src))
+            .map(Self::rsa)
+            .map_err(|_| KeyError::UnsupportedKey)
+    }
+
+    /// Reads the key stored at `path`, PEM encoded if it starts with a PEM header,
+    /// DER encoded otherwise.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, KeyError> {
+        let src = fs::read(path)?;
+
+        match std::str::from_utf8(&src) {
+            Ok(pem) if pem.trim_start().starts_with("-----BEGIN") => Self::from_pem(pem),
+            _                                                      => Self::from_der(&src),
+        }
+    }
+
+    fn rsa(key: RsaPublicKey) -> Self {
+        Self(Key::Rsa(pkcs1v15::VerifyingKey::new(key)))
+    }
+
+    /// Returns `true` if `signature` is the signature of `payload` made with the private key.
+    pub fn verify(&self, payload: &[u8], signature: &[u8]) -> bool {
+        match self.0 {
+            Key::EcdsaP256(ref key) => {
+                ecdsa::Signature::from_der(signature)
+                    .or_else(|_| ecdsa::Signature::from_slice(signature))
+                    .is_ok_and(|x| key.verify(payload, &x).is_ok())
+            }
+            Key::Rsa(ref key) => {
+                pkcs1v15::Signature::try_from(signature)
+                    .is_ok_and(|x| key.verify(payload, &x).is_ok())
+            }
+        }
+    }
+}
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Key::EcdsaP256(_) => f.write_str("PublicKey(EcdsaP256)"),
+            Key::Rsa(_)       => f.write_str("PublicKey(Rsa)"),
+        }
+    }
+}
+
+/// Public keys of the airlines issuing boarding passes, registered per issuer and
+/// type of security data.
+///
+/// ```no_run
+/// use iata::bcbp::{raw, AirlineDesignator, SecurityDataType};
+/// use iata::bcbp::security::{KeyRegistry, Verification};
+///
+/// let mut registry = KeyRegistry::new();
+/// let issuer: AirlineDesignator = "UA".parse().unwrap();
+/// registry.load(issuer, SecurityDataType::DigitalSignature, "keys/ua.pem").unwrap();
+///
+/// fn accept(registry: &KeyRegistry, src: &str) -> bool {
+///     raw::Bcbp::from(src).is_ok_and(|pass| registry.verify(&pass) == Verification::Valid)
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KeyRegistry {
+    keys: HashMap<(AirlineDesignator, SecurityDataType), PublicKey>,
+}
+
+impl KeyRegistry {
+
+    /// Constructs a registry without any key.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the `key` of the `issuer` for the `kind` of security data,
+    /// replacing the previous one.
+    pub fn register(&mut self, issuer: AirlineDesignator, kind: SecurityDataType, key: PublicKey) {
+        self.keys.insert((issuer, kind), key);
+    }
+
+    /// Registers the key stored at `path`, see [`PublicKey::from_file()`].
+    pub fn load<P: AsRef<Path>>(&mut self, issuer: AirlineDesignator, kind: SecurityDataType, path: P) -> Result<(), KeyError> {
+        self.register(issuer, kind, PublicKey::from_file(path)?);
+        Ok(())
+    }
+
+    /// The key of the `issuer` for the `kind` of security data.
+    pub fn get(&self, issuer: AirlineDesignator, kind: SecurityDataType) -> Option<&PublicKey> {
+        self.keys.get(&(issuer, kind))
+    }
+
+    /// Verifies the security data of the `pass` with the key of the airline which
+    /// issued it, given by [`raw::Bcbp::airline_designator_of_boarding_pass_issuer()`].
+    ///
+    /// The pass has to be the one parsed, as the owned [`super::Bcbp`] does not keep
+    /// the data which was signed byte for byte.
+    pub fn verify(&self, pass: &raw::Bcbp) -> Verification {
+        let security = pass.security_data();
+
+        let signature = match security.security_data().map(str::trim) {
+            Some(x) if !x.is_empty() => x,
+            _                        => return Verification::Unsigned,
+        };
+
+        let kind = security.type_of_security_data().unwrap_or_default();
+
+        let key = pass
+            .airline_designator_of_boarding_pass_issuer()
+            .and_then(|x| x.parse().ok())
+            .and_then(|issuer| self.get(issuer, kind));
+
+        let Some(key) = key else {
+            return Verification::UnknownKey
+        };
+
+        match STANDARD.decode(signature) {
+            Ok(x) if key.verify(signed_payload(pass).as_bytes(), &x) => Verification::Valid,
+            _                                                        => Verification::Invalid,
+        }
+    }
+}
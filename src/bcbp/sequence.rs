@@ -0,0 +1,155 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::{Error, ErrorKind, BcbpResult};
+
+/// The check-in sequence number of a passenger, as encoded in item 107.
+///
+/// The number has 4 digits followed by an optional suffix letter, as in `0052A`,
+/// or 5 digits once more than 9999 passengers are checked in.
+/// Numbers come before the other values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "CheckInSequenceRepr"))]
+pub enum CheckInSequence {
+    /// A sequence number, such as `0052A`.
+    Number { number: u32, suffix: Option<char> },
+    /// Any other value up to 5 characters long, defined by the host system, such as `INF`
+    /// for an infant. It is kept as written, without the trailing spaces, and only
+    /// accepted in `Mode::Tolerant`.
+    Other(String),
+}
+
+/// The variants of a [`CheckInSequence`] as they are deserialized, before validation.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+enum CheckInSequenceRepr {
+    Number { number: u32, suffix: Option<char> },
+    Other(String),
+}
+
+/// Validates a number as [`CheckInSequence::new()`] does, and any other value as [`CheckInSequence::other()`].
+#[cfg(feature = "with-serde")]
+impl TryFrom<CheckInSequenceRepr> for CheckInSequence {
+    type Error = Error;
+
+    fn try_from(repr: CheckInSequenceRepr) -> Result<Self, Self::Error> {
+        match repr {
+            CheckInSequenceRepr::Number { number, suffix } => Self::new(number, suffix),
+            CheckInSequenceRepr::Other(s)                  => Self::other(&s),
+        }
+    }
+}
+
+impl CheckInSequence {
+
+    /// Constructs a check-in sequence number.
+    ///
+    /// # Errors
+    /// * [`ErrorKind::MandatoryDataSize`] if `number` does not fit 5 digits,
+    ///   or 4 digits when followed by a `suffix`.
+    /// * [`ErrorKind::AlphaExpected`] if `suffix` is not a letter.
+    pub fn new(number: u32, suffix: Option<char>) -> BcbpResult<Self> {
+
+        let max = if suffix.is_some() { 9999 } else { 99999 };
+
+        if number > max {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if suffix.is_some_and(|x| !x.is_ascii_alphabetic()) {
+            return Err(ErrorKind::AlphaExpected.into())
+        }
+
+        Ok(CheckInSequence::Number { number, suffix })
+    }
+
+    /// Constructs a value defined by the host system, which is not a sequence number.
+    ///
+    /// # Errors
+    /// * [`ErrorKind::MandatoryDataSize`] if `value` is empty or longer than 5 characters,
+    ///   without the trailing spaces.
+    /// * [`ErrorKind::InvalidCharacters`] if `value` holds characters other than printable ASCII.
+    pub fn other(value: &str) -> BcbpResult<Self> {
+        let value = value.trim_end();
+
+        if value.is_empty() || value.len() > 5 {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if !value.bytes().all(|x| x.is_ascii_graphic() || x == b' ') {
+            return Err(ErrorKind::InvalidCharacters.into())
+        }
+
+        Ok(CheckInSequence::Other(value.to_owned()))
+    }
+
+    /// The sequence number, unless the value is defined by the host system.
+    pub fn number(&self) -> Option<u32> {
+        match *self {
+            CheckInSequence::Number { number, .. } => Some(number),
+            CheckInSequence::Other(_)              => None,
+        }
+    }
+
+    /// The letter following the sequence number.
+    pub fn suffix(&self) -> Option<char> {
+        match *self {
+            CheckInSequence::Number { suffix, .. } => suffix,
+            CheckInSequence::Other(_)              => None,
+        }
+    }
+}
+
+/// Writes the number padded to 4 digits followed by the suffix, and any other value
+/// as it is kept, without the trailing spaces.
+///
+/// ```
+/// use iata::bcbp::CheckInSequence;
+///
+/// assert_eq!(CheckInSequence::new(52, Some('A')).unwrap().to_string(), "0052A");
+/// assert_eq!(CheckInSequence::new(52, None).unwrap().to_string(),      "0052");
+/// assert_eq!(CheckInSequence::other("INF  ").unwrap().to_string(),     "INF");
+/// ```
+impl fmt::Display for CheckInSequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckInSequence::Number { number, suffix } => {
+                write!(f, "{:04}", number)?;
+
+                if let Some(suffix) = suffix {
+                    write!(f, "{}", suffix)?;
+                }
+
+                Ok(())
+            }
+            CheckInSequence::Other(s) => f.write_str(s),
+        }
+    }
+}
+
+/// Parses a sequence number, with or without the leading zeroes and the trailing space.
+/// Values defined by the host system are only built with [`CheckInSequence::other()`].
+impl FromStr for CheckInSequence {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_end();
+
+        let (number, suffix) = match s.bytes().last() {
+            Some(x) if x.is_ascii_alphabetic() => (&s[..s.len() - 1], Some(x as char)),
+            _ => (s, None),
+        };
+
+        if number.is_empty() || number.len() > 5 {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if !number.bytes().all(|x| x.is_ascii_digit()) {
+            return Err(ErrorKind::DigitsExpected.into())
+        }
+
+        // Up to five digits, which always fit.
+        Self::new(number.parse().unwrap(), suffix)
+    }
+}
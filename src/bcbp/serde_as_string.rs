@@ -0,0 +1,65 @@
+//! Serializes a [`Bcbp`] as the string encoded in its barcode, for use with
+//! `#[serde(with = "iata::bcbp::serde_as_string")]`.
+//!
+//! The functions at the root of the module build and parse the pass in `Mode::Tolerant`,
+//! the ones of [`strict`] in `Mode::Strict`.
+//!
+//! ```
+//! use iata::bcbp::Bcbp;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Trip {
+//!     #[serde(with = "iata::bcbp::serde_as_string")]
+//!     pass: Bcbp,
+//! }
+//!
+//! let json = r#"{"pass":"M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100"}"#;
+//! let trip: Trip = serde_json::from_str(json).unwrap();
+//!
+//! assert_eq!(trip.pass.name(), "BRUNER/ROMAN MR");
+//! assert_eq!(serde_json::to_string(&trip).unwrap(), json);
+//! ```
+
+use serde::{de, ser, Deserialize, Deserializer, Serializer};
+
+use super::{Bcbp, Mode};
+
+/// Writes the `pass` as built in `Mode::Tolerant`.
+pub fn serialize<S: Serializer>(pass: &Bcbp, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_with_mode(pass, Mode::Tolerant, serializer)
+}
+
+/// Reads a pass parsed in `Mode::Tolerant`.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Bcbp, D::Error> {
+    deserialize_with_mode(deserializer, Mode::Tolerant)
+}
+
+/// Writes the `pass` as built in `mode`.
+pub fn serialize_with_mode<S: Serializer>(pass: &Bcbp, mode: Mode, serializer: S) -> Result<S::Ok, S::Error> {
+    let src = pass.build(mode).map_err(ser::Error::custom)?;
+    serializer.serialize_str(&src)
+}
+
+/// Reads a pass parsed in `mode`.
+pub fn deserialize_with_mode<'de, D: Deserializer<'de>>(deserializer: D, mode: Mode) -> Result<Bcbp, D::Error> {
+    let src = String::deserialize(deserializer)?;
+    Bcbp::from_with_mode(&src, mode).map_err(de::Error::custom)
+}
+
+/// The same functions in `Mode::Strict`, for use with
+/// `#[serde(with = "iata::bcbp::serde_as_string::strict")]`.
+pub mod strict {
+    use serde::{Deserializer, Serializer};
+
+    use super::{Bcbp, Mode};
+
+    /// Writes the `pass` as built in `Mode::Strict`.
+    pub fn serialize<S: Serializer>(pass: &Bcbp, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_with_mode(pass, Mode::Strict, serializer)
+    }
+
+    /// Reads a pass parsed in `Mode::Strict`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Bcbp, D::Error> {
+        super::deserialize_with_mode(deserializer, Mode::Strict)
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::{Error, ErrorKind, BcbpResult};
+
+/// The largest document serial number, 10 digits.
+const MAX_SERIAL: u64 = 9_999_999_999;
+
+/// An electronic ticket number, as encoded in the items 142 and 143.
+///
+/// The 13 digits are divided into:
+///    0... 2: airline numeric code of the issuing carrier.
+///    3...12: document form and serial number with leading zeroes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+pub struct TicketNumber {
+    airline: u16,
+    serial: u64,
+}
+
+impl TicketNumber {
+
+    /// Constructs a ticket number.
+    ///
+    /// # Errors
+    /// * [`ErrorKind::MandatoryDataSize`] if `airline` or `serial` do not fit
+    ///   their 3 and 10 digits.
+    pub fn new(airline: u16, serial: u64) -> BcbpResult<Self> {
+
+        if airline > 999 || serial > MAX_SERIAL {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        Ok(Self { airline, serial })
+    }
+
+    /// Joins the 3-digit airline numeric code and the 10-digit document serial number.
+    ///
+    /// # Errors
+    /// * [`ErrorKind::MandatoryDataSize`] if the parts are not 3 and 10 characters long.
+    /// * [`ErrorKind::DigitsExpected`] if the parts are not made of digits.
+    pub fn from_parts(airline: &str, serial: &str) -> BcbpResult<Self> {
+
+        if airline.len() != 3 || serial.len() != 10 {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if !airline.bytes().chain(serial.bytes()).all(|x| x.is_ascii_digit()) {
+            return Err(ErrorKind::DigitsExpected.into())
+        }
+
+        // Both parts are ASCII digits which fit the numbers.
+        Self::new(airline.parse().unwrap(), serial.parse().unwrap())
+    }
+
+    /// The three-digit numeric code of the airline which issued the ticket.
+    pub fn airline(&self) -> u16 {
+        self.airline
+    }
+
+    /// The ten-digit document form and serial number.
+    pub fn serial(&self) -> u64 {
+        self.serial
+    }
+
+    /// The check digit printed after the ticket number, the remainder of
+    /// the division of the serial number by 7.
+    ///
+    /// ```
+    /// use iata::bcbp::TicketNumber;
+    ///
+    /// let ticket: TicketNumber = "016-1234567890".parse().unwrap();
+    ///
+    /// assert_eq!(ticket.check_digit(), 3);
+    /// assert!(ticket.verify_check_digit(3));
+    /// ```
+    pub fn check_digit(&self) -> u8 {
+        (self.serial % 7) as u8
+    }
+
+    /// Checks the `digit` printed after the ticket number against [`TicketNumber::check_digit()`].
+    pub fn verify_check_digit(&self, digit: u8) -> bool {
+        self.check_digit() == digit
+    }
+
+    /// Iterates over the conjunction tickets from the receiver to the next ticket whose
+    /// serial number ends with the digits `last`, as in `0161234567890/92` or `0161234567899/01`.
+    ///
+    /// ```
+    /// use iata::bcbp::TicketNumber;
+    ///
+    /// let ticket: TicketNumber = "0161234567890".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     ticket.conjunction("92").unwrap().map(|x| x.to_string()).collect::<Vec<_>>(),
+    ///     vec!["0161234567890", "0161234567891", "0161234567892"],
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// * [`ErrorKind::MandatoryDataSize`] if `last` is empty or longer than 10 digits,
+    ///   or if the range runs past the last serial number.
+    /// * [`ErrorKind::DigitsExpected`] if `last` is not made of digits.
+    pub fn conjunction(&self, last: &str) -> BcbpResult<impl Iterator<Item = TicketNumber>> {
+
+        if last.is_empty() || last.len() > 10 {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        if !last.bytes().all(|x| x.is_ascii_digit()) {
+            return Err(ErrorKind::DigitsExpected.into())
+        }
+
+        // Up to 10 digits, which always fit.
+        let modulus = 10u64.pow(last.len() as u32);
+        let mut last = self.serial - self.serial % modulus + last.parse::<u64>().unwrap();
+
+        // The trailing digits of the last ticket rolled over.
+        if last < self.serial {
+            last += modulus;
+        }
+
+        if last > MAX_SERIAL {
+            return Err(ErrorKind::MandatoryDataSize.into())
+        }
+
+        let airline = self.airline;
+
+        Ok((self.serial..=last).map(move |serial| TicketNumber { airline, serial }))
+    }
+}
+
+/// Writes the 13 digits, or the airline code and the serial number separated by
+/// a dash with the alternate flag.
+impl fmt::Display for TicketNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{:03}-{:010}", self.airline, self.serial)
+        } else {
+            write!(f, "{:03}{:010}", self.airline, self.serial)
+        }
+    }
+}
+
+/// Parses the 13 digits, with or without a dash after the airline code.
+impl FromStr for TicketNumber {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        match s.split_once('-') {
+            Some((airline, serial)) => Self::from_parts(airline, serial),
+            None if s.len() == 13 && s.is_ascii() => Self::from_parts(&s[..3], &s[3..]),
+            None if s.is_ascii() => Err(ErrorKind::MandatoryDataSize.into()),
+            None => Err(ErrorKind::InvalidCharacters.into()),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Transliteration of names to the character set of the boarding pass, following the
+//! recommendations of ICAO Doc 9303 Part 3 for the machine readable zone.
+
+/// Transliterates `src` to uppercase letters of the Latin alphabet, digits and spaces.
+///
+/// Latin letters with diacritics, Cyrillic and Greek are spelled as recommended by
+/// ICAO Doc 9303, hyphens become spaces and the characters without an equivalent,
+/// such as apostrophes, are dropped.
+///
+/// ```
+/// use iata::bcbp::transliterate;
+///
+/// assert_eq!(transliterate("Müller-Lüdenscheidt"), "MUELLER LUEDENSCHEIDT");
+/// assert_eq!(transliterate("José"),                "JOSE");
+/// assert_eq!(transliterate("Щербакова"),           "SHCHERBAKOVA");
+/// assert_eq!(transliterate("Παπαδόπουλος"),        "PAPADOPOULOS");
+/// ```
+pub fn transliterate(src: &str) -> String {
+    let mut ret = String::with_capacity(src.len());
+    let mut previous = ' ';
+
+    for c in src.chars().flat_map(char::to_uppercase) {
+        match (previous, c) {
+            (_, 'A'..='Z' | '0'..='9') => ret.push(c),
+            (_, ' ' | '-' | '\t')      => {
+                if !ret.is_empty() && !ret.ends_with(' ') {
+                    ret.push(' ');
+                }
+            },
+            // The Greek digraph OY is spelled OU.
+            ('Ο' | 'Ό', 'Υ' | 'Ύ') => ret.push('U'),
+            _ => ret.push_str(transliterate_char(c)),
+        }
+
+        previous = c;
+    }
+
+    ret.truncate(ret.trim_end().len());
+    ret
+}
+
+/// The spelling of a single uppercase letter, or nothing if it has no equivalent.
+fn transliterate_char(c: char) -> &'static str {
+    match c {
+        // Latin.
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'Ä' | 'Æ'                               => "AE",
+        'Å'                                     => "AA",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č'             => "C",
+        'Ð' | 'Ď' | 'Đ'                         => "D",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ'                   => "G",
+        'Ĥ' | 'Ħ'                               => "H",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'Ĳ'                                     => "IJ",
+        'Ĵ'                                     => "J",
+        'Ķ'                                     => "K",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł'             => "L",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' | 'Ŋ'             => "N",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'Ö' | 'Ø' | 'Œ'                         => "OE",
+        'Ŕ' | 'Ŗ' | 'Ř'                         => "R",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' | 'Ș'             => "S",
+        'ẞ'                                     => "SS",
+        'Ţ' | 'Ť' | 'Ŧ' | 'Ț'                   => "T",
+        'Þ'                                     => "TH",
+        'Ù' | 'Ú' | 'Û' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'Ü'                                     => "UE",
+        'Ŵ'                                     => "W",
+        'Ý' | 'Ÿ' | 'Ŷ'                         => "Y",
+        'Ź' | 'Ż' | 'Ž'                         => "Z",
+
+        // Cyrillic.
+        'А'             => "A",
+        'Б'             => "B",
+        'В'             => "V",
+        'Г' | 'Ґ' | 'Ѓ' => "G",
+        'Д' | 'Ђ'       => "D",
+        'Е' | 'Ё' | 'Э' => "E",
+        'Є' | 'Ъ'       => "IE",
+        'Ж'             => "ZH",
+        'З'             => "Z",
+        'Ѕ' | 'Џ'       => "DZ",
+        'И' | 'І' | 'Ї' | 'Й' => "I",
+        'Ј'             => "J",
+        'К' | 'Ќ'       => "K",
+        'Л'             => "L",
+        'Љ'             => "LJ",
+        'М'             => "M",
+        'Н'             => "N",
+        'Њ'             => "NJ",
+        'О'             => "O",
+        'П'             => "P",
+        'Р'             => "R",
+        'С'             => "S",
+        'Т'             => "T",
+        'Ћ'             => "C",
+        'У' | 'Ў'       => "U",
+        'Ф'             => "F",
+        'Х'             => "KH",
+        'Ц'             => "TS",
+        'Ч'             => "CH",
+        'Ш'             => "SH",
+        'Щ'             => "SHCH",
+        'Ы'             => "Y",
+        'Ю'             => "IU",
+        'Я'             => "IA",
+
+        // Greek.
+        'Α' | 'Ά'             => "A",
+        'Β'                   => "V",
+        'Γ'                   => "G",
+        'Δ'                   => "D",
+        'Ε' | 'Έ'             => "E",
+        'Ζ'                   => "Z",
+        'Η' | 'Ή'             => "I",
+        'Θ'                   => "TH",
+        'Ι' | 'Ί' | 'Ϊ'       => "I",
+        'Κ'                   => "K",
+        'Λ'                   => "L",
+        'Μ'                   => "M",
+        'Ν'                   => "N",
+        'Ξ'                   => "X",
+        'Ο' | 'Ό'             => "O",
+        'Π'                   => "P",
+        'Ρ'                   => "R",
+        'Σ'                   => "S",
+        'Τ'                   => "T",
+        'Υ' | 'Ύ' | 'Ϋ'       => "Y",
+        'Φ'                   => "F",
+        'Χ'                   => "CH",
+        'Ψ'                   => "PS",
+        'Ω' | 'Ώ'             => "O",
+
+        // The soft sign, apostrophes and anything else.
+        _ => "",
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt;
+
+use super::field::Field;
+
+/// Version of Resolution 792 a boarding pass declares in its conditional section.
+///
+/// The fields each version adds decide how much of a conditional section is read as
+/// fields and which fields are written back. Fields beyond the version are kept as
+/// unexpected data or left out, and are not rejected in `Mode::Strict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BcbpVersion {
+    /// Passes issued before the version number was introduced, sometimes referred to as M.0.
+    /// They carry no version marker and leave the variable size field to the airline.
+    /// Their data is kept as the airline individual use of the first leg.
+    /// Displayed as `0`, while a pass which does carry the version number `0` is `Other('0')`.
+    Legacy,
+    /// Version 1, 2005.
+    V1,
+    /// Version 2, 2008. Adds the ID/AD indicator and the free baggage allowance.
+    V2,
+    /// Version 3, 2009. Adds the security data.
+    V3,
+    /// Version 4, 2011. Adds the non-consecutive baggage tag license plates.
+    V4,
+    /// Version 5, 2013. Adds the fast track.
+    V5,
+    /// Version 6, 2016. New values for existing fields only.
+    V6,
+    /// Version 7, 2018. New values for existing fields only.
+    V7,
+    /// Version 8, 2020. New values for existing fields only.
+    V8,
+    /// A version this implementation does not know of.
+    /// It is assumed to keep the layout of the latest known version.
+    Other(char),
+}
+
+const UNIQUE_LEGACY: &[Field] = &[];
+
+const UNIQUE_V1: &[Field] = &[
+    Field::PassengerDescription,
+    Field::SourceOfCheckIn,
+    Field::SourceOfBoardingPassIssuance,
+    Field::DateOfIssueOfBoardingPass,
+    Field::DocumentType,
+    Field::AirlineDesignatorOfBoardingPassIssuer,
+    Field::BaggageTagLicensePlateNumbers,
+];
+
+const UNIQUE_V4: &[Field] = &[
+    Field::PassengerDescription,
+    Field::SourceOfCheckIn,
+    Field::SourceOfBoardingPassIssuance,
+    Field::DateOfIssueOfBoardingPass,
+    Field::DocumentType,
+    Field::AirlineDesignatorOfBoardingPassIssuer,
+    Field::BaggageTagLicensePlateNumbers,
+    Field::FirstNonConsecutiveBaggageTagLicensePlateNumbers,
+    Field::SecondNonConsecutiveBaggageTagLicensePlateNumbers,
+];
+
+const REPEATED_LEGACY: &[Field] = &[];
+
+const REPEATED_V1: &[Field] = &[
+    Field::AirlineNumericCode,
+    Field::DocumentFormSerialNumber,
+    Field::SelecteeIndicator,
+    Field::InternationalDocumentVerification,
+    Field::MarketingCarrierDesignator,
+    Field::FrequentFlyerAirlineDesignator,
+    Field::FrequentFlyerNumber,
+];
+
+const REPEATED_V2: &[Field] = &[
+    Field::AirlineNumericCode,
+    Field::DocumentFormSerialNumber,
+    Field::SelecteeIndicator,
+    Field::InternationalDocumentVerification,
+    Field::MarketingCarrierDesignator,
+    Field::FrequentFlyerAirlineDesignator,
+    Field::FrequentFlyerNumber,
+    Field::IdAdIndicator,
+    Field::FreeBaggageAllowance,
+];
+
+const REPEATED_V5: &[Field] = &[
+    Field::AirlineNumericCode,
+    Field::DocumentFormSerialNumber,
+    Field::SelecteeIndicator,
+    Field::InternationalDocumentVerification,
+    Field::MarketingCarrierDesignator,
+    Field::FrequentFlyerAirlineDesignator,
+    Field::FrequentFlyerNumber,
+    Field::IdAdIndicator,
+    Field::FreeBaggageAllowance,
+    Field::FastTrack,
+];
+
+impl BcbpVersion {
+
+    /// The version assumed for passes which carry conditional data, but no version number.
+    pub const LATEST: BcbpVersion = BcbpVersion::V8;
+
+    pub fn from_char(t: char) -> Self {
+        use BcbpVersion::*;
+        match t {
+            '1' => V1,
+            '2' => V2,
+            '3' => V3,
+            '4' => V4,
+            '5' => V5,
+            '6' => V6,
+            '7' => V7,
+            '8' => V8,
+            _   => Other(t)
+        }
+    }
+
+    pub fn to_char(&self) -> char {
+        use BcbpVersion::*;
+        match *self {
+            Legacy   => '0',
+            V1       => '1',
+            V2       => '2',
+            V3       => '3',
+            V4       => '4',
+            V5       => '5',
+            V6       => '6',
+            V7       => '7',
+            V8       => '8',
+            Other(t) => t
+        }
+    }
+
+    /// Fields of the conditional unique section defined by the version, in order.
+    pub fn unique_fields(self) -> &'static [Field] {
+        use BcbpVersion::*;
+        match self {
+            Legacy       => UNIQUE_LEGACY,
+            V1 | V2 | V3 => UNIQUE_V1,
+            _            => UNIQUE_V4,
+        }
+    }
+
+    /// Fields of the conditional repeated section defined by the version, in order.
+    pub fn repeated_fields(self) -> &'static [Field] {
+        use BcbpVersion::*;
+        match self {
+            Legacy       => REPEATED_LEGACY,
+            V1           => REPEATED_V1,
+            V2 | V3 | V4 => REPEATED_V2,
+            _            => REPEATED_V5,
+        }
+    }
+
+    /// Checks if the field is part of the conditional sections of the version.
+    pub fn defines(self, field: Field) -> bool {
+        self.unique_fields().contains(&field) || self.repeated_fields().contains(&field)
+    }
+
+    /// Length of the conditional unique section when every field is present.
+    pub fn unique_len(self) -> usize {
+        self.unique_fields().iter().map(|x| x.len()).sum()
+    }
+
+    /// Length of the conditional repeated section when every field is present.
+    pub fn repeated_len(self) -> usize {
+        self.repeated_fields().iter().map(|x| x.len()).sum()
+    }
+}
+
+impl fmt::Display for BcbpVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
@@ -12,6 +12,12 @@ pub enum Error {
     #[error("OVERFLOW_NOT_LEAP_YEAR")]
     OverflowNotLeapYear,
 
+    #[error("INVALID_YEAR_DIGIT: {0:?}")]
+    InvalidYearDigit(u8),
+
+    #[error("DATE_IN_FUTURE: {0}")]
+    DateInFuture(time::Date),
+
     #[error("INVALID_DAY_FOR_MONTH: {0:?} {1:?}")]
     InvalidDayForMonth(Month, u8),
 
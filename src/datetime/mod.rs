@@ -1,3 +1,4 @@
+use std::fmt;
 use std::str::FromStr;
 
 mod error;
@@ -22,7 +23,8 @@ const MAX_ADAPT_DAYS: u8 = 31;
 /// This structure DOES NOT guarantee that it contains
 /// a day number valid for any year, since a year maybe contain one extra day.
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "with-serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "u16"))]
 pub struct DayOfYear(u16);
 // pub struct DayOfYearCheck(u32, u8);
 
@@ -89,18 +91,18 @@ impl DayOfYear {
     /// techniques used by the function (here "today's date" is whatever is stored in `for_date`):
     ///
     /// 1. If `self.ordinal()` is more than `days` days earlier than today's date,
-    /// `self.ordinal()` is not more than `days` days later than the start of year -- it
-    /// is considered that `self` belongs to the next year.
+    ///    `self.ordinal()` is not more than `days` days later than the start of year -- it
+    ///    is considered that `self` belongs to the next year.
     /// 2. If `self.ordinal()` is less than `days` days away from year's end, today's date
-    /// is not more than `days` days later than the start of the year -- it is considered that
-    /// `self` belongs to the previous year.
+    ///    is not more than `days` days later than the start of the year -- it is considered that
+    ///    `self` belongs to the previous year.
     /// 3. Otherwise we consider `self` belonging to the same year today's date belongs to.
     ///
     /// # Errors
     ///
     /// * If `days` is greater than 31 -- [`Error::InvalidAdaptRange`] is returned.
     /// * If [`Self::ordinal()`] is 366 and and the algorithm has decided that `self` belongs to
-    /// a non-leap year -- [`Error::OverflowNotLeapYear`] is returned.
+    ///   a non-leap year -- [`Error::OverflowNotLeapYear`] is returned.
     ///
     /// # Examples
     ///
@@ -174,12 +176,156 @@ impl DayOfYear {
     }
 }
 
+impl TryFrom<u16> for DayOfYear {
+    type Error = Error;
+
+    fn try_from(day: u16) -> Result<Self, Self::Error> {
+        Self::new(day)
+    }
+}
+
 impl Default for DayOfYear {
     fn default() -> Self { Self(1) }
 }
 
+/// The 4-digit Julian date of issue of a boarding pass: the last digit of the year
+/// followed by the day of the year. For example "6012" is the 12th day of a year
+/// ending with 6.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "JulianIssueDateRepr"))]
+pub struct JulianIssueDate {
+    year_digit: u8,
+    day: DayOfYear,
+}
+
+/// The fields of a [`JulianIssueDate`] as they are deserialized, before validation.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+struct JulianIssueDateRepr {
+    year_digit: u8,
+    day: DayOfYear,
+}
+
+#[cfg(feature = "with-serde")]
+impl TryFrom<JulianIssueDateRepr> for JulianIssueDate {
+    type Error = Error;
+
+    fn try_from(repr: JulianIssueDateRepr) -> Result<Self, Self::Error> {
+        Self::new(repr.year_digit, repr.day)
+    }
+}
+
+impl JulianIssueDate {
+    /// Constructs a new instance of [`JulianIssueDate`].
+    ///
+    /// # Errors
+    /// If `year_digit` is not from `0..=9` [`Error::InvalidYearDigit`] is returned.
+    pub fn new(year_digit: u8, day: DayOfYear) -> Result<Self, Error> {
+
+        if year_digit > 9 {
+            return Err(Error::InvalidYearDigit(year_digit))
+        }
+
+        Ok(Self { year_digit, day })
+    }
+
+    /// Returns the last digit of the year.
+    pub fn year_digit(&self) -> u8 {
+        self.year_digit
+    }
+
+    /// Returns the day of the year.
+    pub fn day(&self) -> &DayOfYear {
+        &self.day
+    }
+
+    /// Resolves the date against today's date. Equivalent to
+    /// `self.to_date_adapt(today)`.
+    ///
+    /// For more information see [`Self::to_date_adapt()`].
+    pub fn to_date_adapt_year(&self, offset: UtcOffset) -> Result<Date, Error> {
+
+        let now = OffsetDateTime::now_utc().to_offset(offset);
+
+        self.to_date_adapt(now.date())
+    }
+
+    /// Resolves the decade of the date, assuming the boarding pass was issued
+    /// in the latest year ending with [`Self::year_digit()`] which is not after `for_date`.
+    /// A date of issue one day after `for_date` is accepted, as the pass may have been
+    /// issued in a time zone ahead of the one of `for_date`.
+    ///
+    /// # Errors
+    ///
+    /// * If the day is 366 and the resolved year is not a leap one --
+    ///   [`Error::OverflowNotLeapYear`] is returned.
+    /// * If the resolved date is more than a day later than `for_date` -- [`Error::DateInFuture`]
+    ///   is returned, since a boarding pass cannot be issued in the future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iata::datetime::{DayOfYear, JulianIssueDate};
+    /// use time::macros::date;
+    ///
+    /// let issued = JulianIssueDate::new(8, DayOfYear::new(207).unwrap()).unwrap();
+    ///
+    /// assert_eq!(issued.to_date_adapt(date!(2018 - 208)), Ok(date!(2018 - 207)));
+    /// assert_eq!(issued.to_date_adapt(date!(2021 - 001)), Ok(date!(2018 - 207)));
+    /// assert_eq!(issued.to_date_adapt(date!(2018 - 206)), Ok(date!(2018 - 207)));
+    /// assert!(issued.to_date_adapt(date!(2018 - 205)).is_err());
+    /// ```
+    pub fn to_date_adapt(&self, for_date: Date) -> Result<Date, Error> {
+
+        // The latest date the pass may have been issued on, possibly in the next year.
+        let latest = for_date.next_day().unwrap_or(for_date);
+
+        let mut year = latest.year() - (latest.year() - self.year_digit as i32).rem_euclid(10);
+
+        // The year after the one of `for_date` only holds the day allowed for.
+        if year > for_date.year() && self.day.ordinal() > latest.ordinal() {
+            year -= 10;
+        }
+
+        let date = self.day.to_date(year)?;
+
+        if date > latest {
+            return Err(Error::DateInFuture(date))
+        }
+
+        Ok(date)
+    }
+}
+
+impl fmt::Display for JulianIssueDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{:03}", self.year_digit, self.day.ordinal())
+    }
+}
+
+impl FromStr for JulianIssueDate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        if s.len() != 4 || !s.as_bytes().iter().all(u8::is_ascii_digit) {
+            return Err(Error::InvalidInput(s.to_owned()))
+        }
+
+        let (year_digit, day) = s.split_at(1);
+
+        // Both parts are made of ASCII digits only.
+        Self::new(
+            year_digit.parse().unwrap(),
+            DayOfYear::new(day.parse().unwrap())?,
+        )
+    }
+}
+
 /// Enum, which determines the timezone.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TzTag {
     /// No timezone is specified.
     None,
@@ -227,6 +373,7 @@ impl FromStr for TzTag {
 
 /// A basic enum that contains all 12 possible months.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Month {
     January,
     February,
@@ -303,11 +450,30 @@ impl FromStr for Month {
 
 /// A date without a year i.e. a date with a month and a day.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "ShortDateRepr"))]
 pub struct ShortDate {
     month: Month,
     day: u8,
 }
 
+/// The fields of a [`ShortDate`] as they are deserialized, before validation.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+struct ShortDateRepr {
+    month: Month,
+    day: u8,
+}
+
+#[cfg(feature = "with-serde")]
+impl TryFrom<ShortDateRepr> for ShortDate {
+    type Error = Error;
+
+    fn try_from(repr: ShortDateRepr) -> Result<Self, Self::Error> {
+        Self::new(repr.month, repr.day)
+    }
+}
+
 impl ShortDate {
     /// Constructs a short day out of a month and a day. Note, that since
     /// this date isn't aware of the year, it is completely legal to construct
@@ -452,9 +618,9 @@ impl ShortDate {
 
 }
 
-impl ToString for ShortDate {
-    fn to_string(&self) -> String {
-        format!("{:02}{}", self.day, self.month.as_str())
+impl fmt::Display for ShortDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}{}", self.day, self.month.as_str())
     }
 }
 
@@ -492,6 +658,8 @@ impl FromStr for ShortDate {
 /// A struct that stores HH:MM together with its timezone, optionally
 /// including the seconds.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "with-serde", serde(try_from = "TimeRepr"))]
 pub struct Time {
     hour: u8,
     minute: u8,
@@ -499,6 +667,25 @@ pub struct Time {
     timezone: TzTag,
 }
 
+/// The fields of a [`Time`] as they are deserialized, before validation.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+struct TimeRepr {
+    hour: u8,
+    minute: u8,
+    second: Option<u8>,
+    timezone: TzTag,
+}
+
+#[cfg(feature = "with-serde")]
+impl TryFrom<TimeRepr> for Time {
+    type Error = Error;
+
+    fn try_from(repr: TimeRepr) -> Result<Self, Self::Error> {
+        Self::new(repr.hour, repr.minute, repr.second, repr.timezone)
+    }
+}
+
 impl Time {
     /// Constructs the struct out of given values.
     ///
@@ -507,7 +694,7 @@ impl Time {
     /// * Returns [`Error::InvalidHourValue`] if `hour` isn't in the `0..24` range.
     /// * Returns [`Error::InvalidMinuteValue`] if `minute` isn't in the `0..60` range.
     /// * Returns [`Error::InvalidSecondValue`] if `second` is [`Some`] and the value isn't
-    /// in the `0..60` range.
+    ///   in the `0..60` range.
     pub fn new(hour: u8, minute: u8, second: Option<u8>, timezone: TzTag) -> Result<Self, Error> {
 
         if hour > 23 {
@@ -673,6 +860,7 @@ impl Default for Time {
 
 /// A [`ShortDate`] refined with a [`Time`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShortDateTime {
     pub date: ShortDate,
     pub time: Time,
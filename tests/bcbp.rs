@@ -2,9 +2,10 @@ use std::str::from_utf8;
 
 use iata::bcbp::*;
 use iata::datetime::DayOfYear;
+use time::macros::date;
 
 mod samples {
-    use std::{str::from_utf8, num::NonZeroU16};
+    use std::str::from_utf8;
 
     use rand::{seq::{IteratorRandom, IndexedRandom}};
     use iata::{datetime::DayOfYear, bcbp::{Leg, PaxStatus}};
@@ -153,12 +154,24 @@ mod samples {
                 (b'1'..=b'9').choose(&mut rng).unwrap(),
                 (b'1'..=b'9').choose(&mut rng).unwrap(),
                 (b'1'..=b'9').choose(&mut rng).unwrap(),
-                (b'A'..b'Z').choose(&mut rng).unwrap(),
+                (b'A'..=b'Y').choose(&mut rng).unwrap(),
             ],
             *b"INF "
         ].choose(&mut rng).unwrap()
     }
 
+    fn sequence() -> [u8; 5] {
+        let mut rng = rand::rng();
+
+        [
+            (b'0'..=b'9').choose(&mut rng).unwrap(),
+            (b'0'..=b'9').choose(&mut rng).unwrap(),
+            (b'0'..=b'9').choose(&mut rng).unwrap(),
+            (b'0'..=b'9').choose(&mut rng).unwrap(),
+            (b'A'..=b'Z').chain(std::iter::once(b' ')).choose(&mut rng).unwrap(),
+        ]
+    }
+
     fn pax_status() -> u8 {
         let mut rng = rand::rng();
 
@@ -196,25 +209,21 @@ mod samples {
         let flight_day = day_of_year();
         let compartment = compartment();
         let seat = seat();
-        let checking_sequence = rand::random::<NonZeroU16>().get();
+        let checkin_sequence = sequence();
         let pax_status = pax_status();
 
-        let checkin_sequence_bytes = if checking_sequence < 10000 {
-            format!("{checking_sequence:04} ").into_bytes()
-        } else {
-            checking_sequence.to_string().into_bytes()
-        };
         let flight_day_ordinal = flight_day.ordinal();
 
         let mut leg = Leg::default();
         leg.set_pnr(to_utf8(&pnr)).unwrap();
         leg.set_src_airport(to_utf8(&src_airport)).unwrap();
         leg.set_dst_airport(to_utf8(&dst_airport)).unwrap();
-        leg.set_airline(to_utf8(&airline)).unwrap();
+        // A malformed designator, such as `B`, cannot be set and is left out.
+        let _ = leg.set_airline(to_utf8(&airline));
         leg.set_flight_number(to_utf8(&flight_number)).unwrap();
         leg.compartment = Some(compartment as char);
         leg.set_seat(to_utf8(&seat)).unwrap();
-        leg.sequence = Some(checking_sequence);
+        leg.sequence = Some(to_utf8(&checkin_sequence).parse().unwrap());
         leg.flight_day = Some(flight_day);
         leg.pax_status = PaxStatus::from_char(pax_status as char);
         (
@@ -228,7 +237,7 @@ mod samples {
                 .chain(format!("{:03}", flight_day_ordinal).into_bytes())
                 .chain(std::iter::once(compartment))
                 .chain(seat)
-                .chain(checkin_sequence_bytes)
+                .chain(checkin_sequence)
                 .chain(std::iter::once(pax_status))
                 .chain(*b"00")
         )
@@ -257,8 +266,8 @@ fn error_invalid_format_code() {
         let err = Bcbp::from(s).expect_err("Parsing should have failed");
 
         assert_eq!(
-            err,
-            Error::InvalidFormatCode(b as char),
+            err.kind(),
+            ErrorKind::InvalidFormatCode(b as char),
         );
     }
 }
@@ -276,14 +285,15 @@ fn error_invalid_legs_count() {
 
         if b == b'0' {
             assert_eq!(
-                err,
-                Error::InvalidLegsCount,
+                err.kind(),
+                ErrorKind::InvalidLegsCount,
             );
         } else {
             assert_eq!(
-                err,
-                Error::ExpectedInteger(field::Field::NumberOfLegsEncoded),
+                err.kind(),
+                ErrorKind::ExpectedInteger,
             );
+            assert_eq!(err.field(), Some(field::Field::NumberOfLegsEncoded));
         }
     }
 }
@@ -291,11 +301,11 @@ fn error_invalid_legs_count() {
 #[test]
 fn error_data_size() {
     if let Err(e) = Bcbp::from("") {
-        assert!(e == Error::MandatoryDataSize);
+        assert!(e.kind() == ErrorKind::MandatoryDataSize);
     }
 
     if let Err(e) = Bcbp::from("M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 1FF") {
-        assert!(e == Error::CoditionalDataSize);
+        assert!(e.kind() == ErrorKind::CoditionalDataSize);
     }
 }
 
@@ -310,7 +320,7 @@ fn minimal() {
             src.extend(name.fullname);
             src.push(b' ');
             src.extend(**pnr);
-            src.extend(std::iter::repeat(b' ').take(28));
+            src.extend(std::iter::repeat_n(b' ', 28));
             src.extend(b"00");
 
             assert_eq!(src.len(), 60);
@@ -327,7 +337,7 @@ fn minimal() {
             assert_eq!(bcbp.name(),        from_utf8(name.fullname).unwrap().trim());
             assert_eq!(bcbp.name_last,     from_utf8(name.lastname).unwrap());
             assert_eq!(bcbp.name_first.as_deref(),    name.firstname.map(|x| from_utf8(x).unwrap()));
-            assert_eq!(bcbp.ticket_flag,   None);
+            assert_eq!(bcbp.ticket_flag,   TicketIndicator::None);
             assert_eq!(bcbp.version,       None);
 
             assert_eq!(bcbp.legs[0].pnr(),           Some(from_utf8(*pnr).unwrap().trim()));
@@ -378,23 +388,27 @@ fn mandatory_legs() {
                 assert_eq!(bcbp.name(),        from_utf8(name.fullname).unwrap().trim());
                 assert_eq!(bcbp.name_last,     from_utf8(name.lastname).unwrap());
                 assert_eq!(bcbp.name_first.as_deref(),    name.firstname.map(|x| from_utf8(x).unwrap()));
-                assert_eq!(bcbp.ticket_flag,   Some('E'));
+                assert_eq!(bcbp.ticket_flag,   TicketIndicator::ElectronicTicket);
                 assert_eq!(bcbp.version,       None);
 
-                for i in 0..leg_count {
-                    assert_eq!(bcbp.legs[i].pnr(),            legs[i].pnr());
-                    assert_eq!(bcbp.legs[i].src_airport(),    legs[i].src_airport());
-                    assert_eq!(bcbp.legs[i].dst_airport(),    legs[i].dst_airport());
-                    assert_eq!(bcbp.legs[i].airline(),        legs[i].airline());
-                    assert_eq!(bcbp.legs[i].flight_number(),  legs[i].flight_number());
-                    assert_eq!(bcbp.legs[i].flight_day,       legs[i].flight_day);
-                    assert_eq!(bcbp.legs[i].compartment,      legs[i].compartment);
-                    assert_eq!(bcbp.legs[i].seat(),           legs[i].seat());
-                    assert_eq!(bcbp.legs[i].sequence,         legs[i].sequence);
-                    assert_eq!(bcbp.legs[i].pax_status,       legs[i].pax_status);
+                for (i, leg) in legs.iter().enumerate() {
+                    assert_eq!(bcbp.legs[i].pnr(),            leg.pnr());
+                    assert_eq!(bcbp.legs[i].src_airport(),    leg.src_airport());
+                    assert_eq!(bcbp.legs[i].dst_airport(),    leg.dst_airport());
+                    match leg.airline() {
+                        Some(airline) => assert_eq!(bcbp.legs[i].airline(), Some(airline)),
+                        None          => assert!(!bcbp.legs[i].airline().unwrap().is_valid()),
+                    }
+                    assert_eq!(bcbp.legs[i].flight_number(),  leg.flight_number());
+                    assert_eq!(bcbp.legs[i].flight_day,       leg.flight_day);
+                    assert_eq!(bcbp.legs[i].compartment,      leg.compartment);
+                    assert_eq!(bcbp.legs[i].seat(),           leg.seat());
+                    assert_eq!(bcbp.legs[i].sequence,         leg.sequence);
+                    assert_eq!(bcbp.legs[i].pax_status,       leg.pax_status);
                 }
 
-                assert_eq!(bcbp.build(Mode::Tolerant).expect("Building shouldn't fail"), src);
+                let built = bcbp.build(Mode::Tolerant).expect("Building shouldn't fail");
+                assert_eq!(built, src);
             }
         }
     }
@@ -415,25 +429,29 @@ fn home_printed_1_1() {
     assert_eq!(bcbp.name(),      "TEST/HIDDEN");
     assert_eq!(bcbp.name_last,   "TEST");
     assert_eq!(bcbp.name_first,  Some("HIDDEN".to_string()));
-    assert_eq!(bcbp.ticket_flag, Some('E'));
-    assert_eq!(bcbp.version,     Some('2'));
+    assert_eq!(bcbp.ticket_flag, TicketIndicator::ElectronicTicket);
+    assert_eq!(bcbp.version,     Some(BcbpVersion::V2));
     assert_eq!(bcbp.pax_type,    PaxType::Adult);
 
-    assert_eq!(bcbp.checkin_src,                     Some('W'));
-    assert_eq!(bcbp.boardingpass_src,                Some('W'));
-    assert_eq!(bcbp.boardingpass_issued,             Some(6012));
-    assert_eq!(bcbp.doc_type,                        Some('B'));
-    assert_eq!(bcbp.boardingpass_airline(),          Some("LH"));
-
-    assert_eq!(bcbp.legs[0].pnr().as_deref(),           Some("8OQ6FU"));
-    assert_eq!(bcbp.legs[0].src_airport().as_deref(),   Some("FRA"));
-    assert_eq!(bcbp.legs[0].dst_airport().as_deref(),   Some("RLG"));
-    assert_eq!(bcbp.legs[0].airline().as_deref(),       Some("LH"));
-    assert_eq!(bcbp.legs[0].flight_number().as_deref(), Some("4010"));
+    assert_eq!(bcbp.checkin_src,                     CheckInSource::Web);
+    assert_eq!(bcbp.boardingpass_src,                BoardingPassSource::WebPrinted);
+    assert_eq!(bcbp.boardingpass_issued,             "6012".parse().ok());
+    assert_eq!(bcbp.issue_date(date!(2016 - 02 - 01)), Ok(Some(date!(2016 - 01 - 12))));
+    assert_eq!(bcbp.issue_date(date!(2025 - 12 - 31)), Ok(Some(date!(2016 - 01 - 12))));
+    assert_eq!(bcbp.issue_date(date!(2016 - 01 - 11)), Ok(Some(date!(2016 - 01 - 12))));
+    assert!(bcbp.issue_date(date!(2016 - 01 - 10)).is_err());
+    assert_eq!(bcbp.doc_type,                        DocumentType::BoardingPass);
+    assert_eq!(bcbp.boardingpass_airline().unwrap(),      "LH");
+
+    assert_eq!(bcbp.legs[0].pnr(),           Some("8OQ6FU"));
+    assert_eq!(bcbp.legs[0].src_airport().unwrap(),   "FRA");
+    assert_eq!(bcbp.legs[0].dst_airport().unwrap(),   "RLG");
+    assert_eq!(bcbp.legs[0].airline().unwrap(),   "LH");
+    assert_eq!(bcbp.legs[0].flight_number(), Some("4010"));
     assert_eq!(bcbp.legs[0].flight_day,                 Some(DayOfYear::new(12).unwrap()));
     assert_eq!(bcbp.legs[0].compartment,                Some('C'));
-    assert_eq!(bcbp.legs[0].seat().as_deref(),          Some("4D"));
-    assert_eq!(bcbp.legs[0].sequence,                   Some(1));
+    assert_eq!(bcbp.legs[0].seat(),          Some(&Seat::Assigned { row: 4, letter: 'D' }));
+    assert_eq!(bcbp.legs[0].sequence,                   "0001".parse().ok());
     assert_eq!(bcbp.legs[0].pax_status,                 PaxStatus::Other('3'));
 }
 
@@ -489,26 +507,874 @@ fn conditional3() {
     assert!(bcbp.name()     == "JOHN/SMITH");
     assert!(bcbp.name_last  == "JOHN");
     assert!(bcbp.name_first == Some("SMITH".to_string()));
-    assert!(bcbp.ticket_flag == Some('E'));
+    assert!(bcbp.ticket_flag == TicketIndicator::ElectronicTicket);
 
     assert_eq!(bcbp.legs[0].pnr(),           Some("ABCDEF"));
-    assert_eq!(bcbp.legs[0].src_airport(),   Some("JFK"));
-    assert_eq!(bcbp.legs[0].dst_airport(),   Some("SVO"));
-    assert_eq!(bcbp.legs[0].airline(),       Some("SK"));
+    assert_eq!(bcbp.legs[0].src_airport().unwrap(),   "JFK");
+    assert_eq!(bcbp.legs[0].dst_airport().unwrap(),   "SVO");
+    assert_eq!(bcbp.legs[0].airline().unwrap(),   "SK");
     assert_eq!(bcbp.legs[0].flight_number(), Some("1234"));
     assert_eq!(bcbp.legs[0].flight_day,      Some(DayOfYear::new(123).unwrap()));
 
     assert_eq!(bcbp.legs[1].pnr(),           Some("ABCDEF"));
-    assert_eq!(bcbp.legs[1].src_airport(),   Some("SVO"));
-    assert_eq!(bcbp.legs[1].dst_airport(),   Some("FRA"));
-    assert_eq!(bcbp.legs[1].airline(),       Some("SU"));
+    assert_eq!(bcbp.legs[1].src_airport().unwrap(),   "SVO");
+    assert_eq!(bcbp.legs[1].dst_airport().unwrap(),   "FRA");
+    assert_eq!(bcbp.legs[1].airline().unwrap(),   "SU");
     assert_eq!(bcbp.legs[1].flight_number(), Some("5678"));
     assert_eq!(bcbp.legs[1].flight_day,      Some(DayOfYear::new(135).unwrap()));
 
     assert_eq!(bcbp.legs[2].pnr(),           Some("ABCDEF"));
-    assert_eq!(bcbp.legs[2].src_airport(),   Some("FRA"));
-    assert_eq!(bcbp.legs[2].dst_airport(),   Some("JFK"));
-    assert_eq!(bcbp.legs[2].airline(),       Some("SU"));
+    assert_eq!(bcbp.legs[2].src_airport().unwrap(),   "FRA");
+    assert_eq!(bcbp.legs[2].dst_airport().unwrap(),   "JFK");
+    assert_eq!(bcbp.legs[2].airline().unwrap(),   "SU");
     assert_eq!(bcbp.legs[2].flight_number(), Some("9876"));
     assert_eq!(bcbp.legs[2].flight_day,      Some(DayOfYear::new(231).unwrap()));
 }
+
+#[test]
+fn build_conditional_exact() {
+    let src = "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 316>503  W0D0742497067621";
+    let bcbp = Bcbp::from(src).unwrap();
+
+    assert_eq!(bcbp.build(Mode::Tolerant).unwrap(), src);
+}
+
+#[test]
+fn build_conditional_roundtrip() {
+    let samples = &[
+        "M1TEST/HIDDEN         E8OQ6FU FRARLGLH 4010 012C004D0001 35C>2180WW6012BLH              2922023642241060 LH                        *30600000K09         ",
+        "M1ASKREN/TEST         EA272SL ORDNRTUA 0881 007F002K0303 15C>3180 M6007BUA              2901624760758980 UA UA EY975897            *30600    09  UAG    ^160MEYCIQCVDy6sskR0zx8Ac5aXCG0hjkejH587woSGHWnbBRbp8QIhAJ790UHbTHG9nZLnllP+JjStGWPLWGR7Ag5on2FPCeRG",
+        "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ^164GIWVC5EH7JNT684FVNJ91W2QA4DVN5J8K4F0L0GEQ3DF5TGBN8709HKT5D3DW3GBHFCVHMY7J5T6HFR41W2QA4DVN5J8K4F0L0GE",
+        "M3JOHN/SMITH          EABCDEF JFKSVOSK 1234 123M014C0050 35D>5180O 0276BSK              2A55559467513980 SK                         *30600000K09         ABCDEF SVOFRASU 5678 135Y013A0012 3372A55559467513990 SU SU 12345678             09         ABCDEF FRAJFKSU 9876 231Y022F0052 3372A55559467513990 SU SU 12345678             09         ",
+        "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100^100",
+    ];
+
+    for src in samples {
+        let bcbp = Bcbp::from(src).unwrap();
+        let built = bcbp.build(Mode::Tolerant).unwrap();
+
+        assert_eq!(Bcbp::from(&built).unwrap(), bcbp, "{built}");
+    }
+}
+
+#[test]
+fn build_version_only() {
+    let mut bcbp = Bcbp::from(samples::BASE_BCBP).unwrap();
+    bcbp.version = Some(BcbpVersion::V6);
+
+    assert_eq!(
+        bcbp.build(Mode::Tolerant).unwrap(),
+        "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 104>600"
+    );
+
+    bcbp.legs[0].var = Some("ABC".into());
+
+    assert_eq!(
+        bcbp.build(Mode::Tolerant).unwrap(),
+        "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 109>60000ABC"
+    );
+}
+
+#[test]
+fn strict_parsing() {
+    let samples = &[
+        samples::BASE_BCBP,
+        "M1ASKREN/TEST         EA272SL ORDNRTUA 0881 007F002K0303 15C>3180 M6007BUA              2901624760758980 UA UA EY975897            *30600    09  UAG    ^160MEYCIQCVDy6sskR0zx8Ac5aXCG0hjkejH587woSGHWnbBRbp8QIhAJ790UHbTHG9nZLnllP+JjStGWPLWGR7Ag5on2FPCeRG",
+        "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ^164GIWVC5EH7JNT684FVNJ91W2QA4DVN5J8K4F0L0GEQ3DF5TGBN8709HKT5D3DW3GBHFCVHMY7J5T6HFR41W2QA4DVN5J8K4F0L0GE",
+    ];
+
+    for src in samples {
+        assert_eq!(
+            Bcbp::from_with_mode(src, Mode::Strict).unwrap(),
+            Bcbp::from_with_mode(src, Mode::Tolerant).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn strict_rejects_quirks() {
+    // Mixed case name from a real Air Canada pass.
+    let src = "M1Mroz/Martin         EXXXXXX YVRYOWAC 0344 211          072>20B0  8203IAC 250140000000000 0AC AC AC000000000     *20000AC 223                14080003068        0B          N";
+    assert!(Bcbp::from_with_mode(src, Mode::Tolerant).is_ok());
+    assert_eq!(Bcbp::from_with_mode(src, Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::InvalidCharacters));
+
+    // Non-numeric date of flight.
+    let src = "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 23XL013A0052 100";
+    assert_eq!(Bcbp::from_with_mode(src, Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::DigitsExpected));
+
+    // Padding with something other than spaces.
+    let src = "M1BRUNER/ROMAN MR\0\0\0\0\0EJNUFFX MUCSVOSU 2327 231L013A0052 100";
+    assert!(Bcbp::from_with_mode(src, Mode::Tolerant).is_ok());
+    assert_eq!(Bcbp::from_with_mode(src, Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::InvalidCharacters));
+
+    let src = "M1BRUNER/ROMAN MR.....EJNUFFX MUCSVOSU 2327 231L013A0052 100";
+    assert!(Bcbp::from_with_mode(src, Mode::Tolerant).is_ok());
+    let err = Bcbp::from_with_mode(src, Mode::Strict).unwrap_err();
+    assert_eq!(err.kind(),   ErrorKind::InvalidCharacters);
+    assert_eq!(err.field(),  Some(field::Field::PassengerName));
+
+    let src = "M1BRUNER/ROMAN MR     EEJNUF__MUCSVOSU 2327 231L013A0052 100";
+    assert!(Bcbp::from_with_mode(src, Mode::Tolerant).is_ok());
+    let err = Bcbp::from_with_mode(src, Mode::Strict).unwrap_err();
+    assert_eq!(err.kind(),   ErrorKind::InvalidCharacters);
+    assert_eq!(err.field(),  Some(field::Field::OperatingCarrierPnrCode));
+    assert_eq!(err.offset(), Some(23));
+
+    // Punctuation within the value is not padding.
+    let src = "M1BRUNER/ROMAN MR     EEJ-UFFXMUCSVOSU 2327 231L013A0052 100";
+    assert!(Bcbp::from_with_mode(src, Mode::Strict).is_ok());
+
+    // Lowercase hexadecimal size.
+    let src = "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 316>503  W0d0742497067621";
+    assert!(Bcbp::from_with_mode(src, Mode::Tolerant).is_ok());
+    let err = Bcbp::from_with_mode(src, Mode::Strict).unwrap_err();
+    assert_eq!(err.kind(),   ErrorKind::ExpectedInteger);
+    assert_eq!(err.field(),  Some(field::Field::FieldSizeOfStructuredMessageRepeated));
+    assert_eq!(err.offset(), Some(67));
+    assert_eq!(err.leg(),    Some(0));
+
+    // The repeated section of the second leg claims a byte of the airline individual use.
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2B0140987654321 1AC AC 1234567890123    2PCNWQ";
+    assert!(Bcbp::from_with_mode(src, Mode::Tolerant).is_ok());
+    assert_eq!(Bcbp::from_with_mode(src, Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::CoditionalDataSize));
+}
+
+#[test]
+fn strict_building() {
+    let mut bcbp = Bcbp::default();
+    assert_eq!(bcbp.build(Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::InvalidLegsCount));
+
+    bcbp.name_last = "TEST".into();
+    bcbp.ticket_flag = TicketIndicator::ElectronicTicket;
+    bcbp.legs.push(Leg::default());
+    let err = bcbp.build(Mode::Strict).unwrap_err();
+    assert_eq!(err.kind(),  ErrorKind::MandatoryFieldMissing);
+    assert_eq!(err.field(), Some(field::Field::OperatingCarrierPnrCode));
+    assert_eq!(err.leg(),   Some(0));
+    assert_eq!(err.to_string(), "MANDATORY_FIELD_MISSING in Operating Carrier PNR Code of leg 0");
+
+    let mut bcbp = Bcbp::from("M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 316>503  W0D0742497067621").unwrap();
+    assert!(bcbp.build(Mode::Strict).is_ok());
+
+    bcbp.version = None;
+    assert!(bcbp.build(Mode::Tolerant).is_ok());
+    let err = bcbp.build(Mode::Strict).unwrap_err();
+    assert_eq!(err.kind(),  ErrorKind::MandatoryFieldMissing);
+    assert_eq!(err.field(), Some(field::Field::VersionNumber));
+
+    let mut bcbp = Bcbp::from(samples::BASE_BCBP).unwrap();
+    assert_eq!(bcbp.build(Mode::Strict).unwrap(), samples::BASE_BCBP);
+
+    // The name is written in the characters of the pass.
+    bcbp.name_last = "Bruner".into();
+    assert_eq!(bcbp.build(Mode::Strict).unwrap(), samples::BASE_BCBP);
+
+    let _ = bcbp.legs_mut()[0].set_pnr("jnuffx");
+    assert!(bcbp.build(Mode::Tolerant).is_ok());
+    assert_eq!(bcbp.build(Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::InvalidCharacters));
+}
+
+#[test]
+fn security_indicators() {
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let bcbp = Bcbp::from(src).unwrap();
+
+    for leg in bcbp.legs() {
+        assert_eq!(leg.selectee(),         SelecteeIndicator::None);
+        assert_eq!(leg.doc_verification(), DocumentVerification::Required);
+        assert_eq!(leg.id_ad(),            IdAdIndicator::None);
+    }
+
+    let src = "M1MROZ/MARTIN         EXXXXXX SJCLAXAS 3317 207U001A0006 34D>218 VV8207BAS              2502771980993865 AS AS XXXXX55200000000Z29  00010";
+    let bcbp = Bcbp::from(src).unwrap();
+
+    assert_eq!(bcbp.legs[0].selectee(),         SelecteeIndicator::Other('5'));
+    assert_eq!(bcbp.legs[0].doc_verification(), DocumentVerification::None);
+    assert_eq!(bcbp.legs[0].id_ad(),            IdAdIndicator::None);
+    assert_eq!(bcbp.legs[0].selectee().description(), "Unknown");
+}
+
+#[test]
+fn build_security_indicators() {
+    let mut bcbp = Bcbp::from("M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 316>503  W0D0742497067621").unwrap();
+
+    bcbp.legs[0].set_selectee(SelecteeIndicator::Selectee).unwrap();
+    assert_eq!(
+        bcbp.build(Mode::Tolerant).unwrap(),
+        "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 317>503  W0E07424970676211"
+    );
+
+    bcbp.legs[0].set_id_ad(IdAdIndicator::Idb1PositiveSpace).unwrap();
+    let built = bcbp.build(Mode::Tolerant).unwrap();
+    assert_eq!(
+        built,
+        "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 32F>503  W2607424970676211                       2"
+    );
+
+    let parsed = Bcbp::from(&built).unwrap();
+    assert_eq!(parsed.legs[0].selectee(), SelecteeIndicator::Selectee);
+    assert_eq!(parsed.legs[0].id_ad(),    IdAdIndicator::Idb1PositiveSpace);
+
+    bcbp.legs[0].set_selectee(SelecteeIndicator::None).unwrap();
+    assert_eq!(bcbp.legs[0].selectee(), SelecteeIndicator::None);
+    assert_eq!(bcbp.legs[0].set_selectee(SelecteeIndicator::Other('\0')).map_err(|e| e.kind()), Err(ErrorKind::InvalidCharacters));
+}
+
+#[test]
+fn code_tables() {
+    for c in ascii_bytes().map(char::from) {
+        assert_eq!(TicketIndicator::from_char(c).to_char(),      c);
+        assert_eq!(CheckInSource::from_char(c).to_char(),        c);
+        assert_eq!(BoardingPassSource::from_char(c).to_char(),   c);
+        assert_eq!(DocumentType::from_char(c).to_char(),         c);
+        assert_eq!(SelecteeIndicator::from_char(c).to_char(),    c);
+        assert_eq!(DocumentVerification::from_char(c).to_char(), c);
+        assert_eq!(IdAdIndicator::from_char(c).to_char(),        c);
+        assert_eq!(FastTrack::from_char(c).to_char(),            c);
+        assert_eq!(SecurityDataType::from_char(c).to_char(),     c);
+    }
+
+    assert_eq!(BoardingPassSource::from_char('X'), BoardingPassSource::TransferKiosk);
+    assert_eq!(BoardingPassSource::TransferKiosk.description(), "Transfer kiosk");
+    assert_eq!(IdAdIndicator::from_char('A'), IdAdIndicator::Ud);
+    assert_eq!(FastTrack::from_char(' '), FastTrack::None);
+    assert_eq!(FastTrack::from_char('y'), FastTrack::Other('y'));
+    assert_eq!(FastTrack::Other('y').description(), "Unknown");
+
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let mut bcbp = Bcbp::from(src).unwrap();
+    assert_eq!(bcbp.checkin_src,                 CheckInSource::Web);
+    assert_eq!(bcbp.legs[1].doc_verification(), DocumentVerification::Required);
+    assert_eq!(bcbp.legs[1].fast_track,          FastTrack::NotEligible);
+
+    bcbp.checkin_src = CheckInSource::MobileDevice;
+    bcbp.legs[1].fast_track = FastTrack::None;
+    let parsed = Bcbp::from(&bcbp.build(Mode::Tolerant).unwrap()).unwrap();
+    assert_eq!(parsed.checkin_src,         CheckInSource::MobileDevice);
+    assert_eq!(parsed.legs[1].fast_track,  FastTrack::None);
+}
+
+#[test]
+fn pax_type() {
+    for c in ascii_bytes().map(char::from) {
+        assert_eq!(PaxType::from_char(c).to_char(), c);
+    }
+
+    assert_eq!(PaxType::from_char('5'), PaxType::CabinBaggage);
+    assert_eq!(PaxType::from_char('6'), PaxType::AdultWithInfant);
+    assert_eq!(PaxType::from_char('7'), PaxType::UnaccompaniedMinor);
+    assert_eq!(PaxType::from_char('A'), PaxType::Other('A'));
+
+    let src = "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 316>503  W0D0742497067621";
+    let mut bcbp = Bcbp::from(src).unwrap();
+    assert_eq!(bcbp.pax_type, PaxType::None);
+
+    bcbp.pax_type = PaxType::AdultWithInfant;
+    let built = bcbp.build(Mode::Strict).unwrap();
+    assert_eq!(built, "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 316>5036 W0D0742497067621");
+    assert_eq!(Bcbp::from(&built).unwrap().pax_type, PaxType::AdultWithInfant);
+}
+
+#[test]
+fn seats() {
+    assert_eq!("012C".parse(), Seat::new(12, 'C'));
+    assert_eq!("12C".parse(),  Seat::new(12, 'C'));
+    assert_eq!("INF ".parse(), Ok(Seat::Infant));
+    assert_eq!("GATE".parse(), Ok(Seat::Gate));
+    assert_eq!("STBY".parse(), Ok(Seat::Standby));
+    assert_eq!("NS".parse(),   Ok(Seat::NoSeat));
+    assert_eq!("000C".parse(), Ok(Seat::Other("000C".into())));
+    assert_eq!("JS1".parse(),  Ok(Seat::Other("JS1".into())));
+    assert_eq!("12345".parse::<Seat>().map_err(|e| e.kind()), Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(Seat::new(12, 'c').map_err(|e| e.kind()), Err(ErrorKind::AlphaExpected));
+
+    assert_eq!(Seat::new(7, 'A').unwrap().to_string(), "007A");
+    assert_eq!(Seat::NoSeat.to_string(),               "NS  ");
+    assert_eq!(Seat::new(12, 'C').unwrap().row(),      Some(12));
+    assert_eq!(Seat::Gate.letter(),                    None);
+
+    let mut seats = [Seat::Infant, Seat::new(12, 'C').unwrap(), Seat::new(9, 'F').unwrap(), Seat::new(12, 'A').unwrap()];
+    seats.sort();
+    assert_eq!(seats.iter().map(ToString::to_string).collect::<Vec<_>>(), vec!["009F", "012A", "012C", "INF "]);
+
+    let mut bcbp = Bcbp::from(samples::BASE_BCBP).unwrap();
+    bcbp.legs[0].set_seat("STBY").unwrap();
+    assert_eq!(bcbp.build(Mode::Strict).unwrap(), "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231LSTBY0052 100");
+    bcbp.legs[0].set_seat("").unwrap();
+    assert_eq!(bcbp.legs[0].seat(), None);
+}
+
+#[test]
+fn flight_designators() {
+    use std::collections::HashSet;
+
+    let lh400a = FlightDesignator::new("LH", 400, Some('A')).unwrap();
+    assert_eq!("LH400A".parse(),   Ok(lh400a));
+    assert_eq!("LH 0400A".parse(), Ok(lh400a));
+    assert_eq!(FlightDesignator::from_bcbp("LH 0400A"), Ok(lh400a));
+    assert_eq!(lh400a.to_string(),            "LH400A");
+    assert_eq!(format!("{:#}", lh400a),       "LH 0400A");
+
+    let ezy = FlightDesignator::new("EZY", 1234, None).unwrap();
+    assert_eq!("EZY1234".parse(),  Ok(ezy));
+    assert_eq!(ezy.to_bcbp(),      "EZY1234 ");
+    assert_eq!("U2123".parse::<FlightDesignator>().unwrap().carrier(), "U2");
+    assert_eq!(FlightDesignator::from_bcbp("3K 834  ").unwrap().number(), 834);
+
+    assert_eq!("LH".parse::<FlightDesignator>().map_err(|e| e.kind()),        Err(ErrorKind::MandatoryDataSize));
+    assert_eq!("LH12345".parse::<FlightDesignator>().map_err(|e| e.kind()),   Err(ErrorKind::DigitsExpected));
+    assert_eq!(FlightDesignator::new("L", 1, None).map_err(|e| e.kind()),     Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(FlightDesignator::new("L-", 1, None).map_err(|e| e.kind()),    Err(ErrorKind::AlphaNumExpected));
+    assert_eq!(FlightDesignator::new("LH", 0, None).map_err(|e| e.kind()),    Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(FlightDesignator::new("LH", 1, Some('1')).map_err(|e| e.kind()), Err(ErrorKind::AlphaExpected));
+
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let mut bcbp = Bcbp::from(src).unwrap();
+    let schedule: HashSet<FlightDesignator> = ["AC834".parse().unwrap(), "LH3664".parse().unwrap()].into();
+    assert!(bcbp.legs().iter().all(|x| schedule.contains(&x.flight_designator().unwrap())));
+
+    let raw = raw::Bcbp::from(src).unwrap();
+    assert_eq!(raw.legs()[1].flight_designator(), bcbp.legs[1].flight_designator());
+
+    bcbp.legs[0].set_flight_designator(&lh400a);
+    assert_eq!(bcbp.legs[0].airline().unwrap(),   "LH");
+    assert_eq!(bcbp.legs[0].flight_number(), Some("0400A"));
+    assert!(bcbp.build(Mode::Strict).unwrap().contains("YULFRALH 0400A226F"));
+}
+
+#[test]
+fn designators() {
+    let fra: AirportCode = "FRA".parse().unwrap();
+    let copy = fra;
+    assert_eq!(fra, copy);
+    assert_eq!(fra, "FRA");
+    assert_eq!(fra.to_string(), "FRA");
+    assert!(fra < "MUC".parse().unwrap());
+
+    assert_eq!("FR".parse::<AirportCode>().map_err(|e| e.kind()),   Err(ErrorKind::MandatoryDataSize));
+    assert_eq!("FRAN".parse::<AirportCode>().map_err(|e| e.kind()), Err(ErrorKind::MandatoryDataSize));
+    assert_eq!("FR1".parse::<AirportCode>().map_err(|e| e.kind()),  Err(ErrorKind::AlphaExpected));
+
+    let b6: AirlineDesignator = "B6 ".parse().unwrap();
+    assert_eq!(b6, "B6");
+    assert_eq!(Ok(b6), "B6".parse());
+    assert_eq!(b6.as_str(), "B6");
+    assert_eq!("EZY".parse::<AirlineDesignator>().unwrap().to_string(), "EZY");
+
+    assert_eq!("B".parse::<AirlineDesignator>().map_err(|e| e.kind()),    Err(ErrorKind::MandatoryDataSize));
+    assert_eq!("EZYX".parse::<AirlineDesignator>().map_err(|e| e.kind()), Err(ErrorKind::MandatoryDataSize));
+    assert_eq!("B-".parse::<AirlineDesignator>().map_err(|e| e.kind()),   Err(ErrorKind::AlphaNumExpected));
+    assert_eq!("EZ1".parse::<AirlineDesignator>().map_err(|e| e.kind()),  Err(ErrorKind::AlphaNumExpected));
+
+    let mut leg = Leg::default();
+    assert_eq!(leg.set_src_airport("MU1").map_err(|e| e.kind()), Err(ErrorKind::AlphaExpected));
+    assert_eq!(leg.set_airline("B6 "),  Ok(()));
+    assert_eq!(leg.airline(),           Some(b6));
+    assert_eq!(leg.set_airline(""),     Ok(()));
+    assert_eq!(leg.airline(),           None);
+
+    // Malformed codes are kept as they are with a warning, unless in `Mode::Strict`.
+    let src = "M1BRUNER/ROMAN MR     EJNUFFX MUCSV1B  2327 231L013A0052 100";
+    let bcbp = Bcbp::from(src).unwrap();
+    assert_eq!(bcbp.legs[0].src_airport().unwrap(), "MUC");
+    assert!(bcbp.legs[0].src_airport().unwrap().is_valid());
+    assert_eq!(bcbp.legs[0].dst_airport().unwrap(), "SV1");
+    assert!(!bcbp.legs[0].dst_airport().unwrap().is_valid());
+    assert_eq!(bcbp.legs[0].airline().unwrap(),     "B");
+    assert!(!bcbp.legs[0].airline().unwrap().is_valid());
+    assert_eq!(bcbp.build(Mode::Tolerant).unwrap(), src);
+    assert_eq!(Bcbp::from_with_mode(src, Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::AlphaExpected));
+
+    let (_, diagnostics) = Bcbp::diagnose(src);
+    let fields: Vec<_> = diagnostics.iter().map(|x| (x.field, x.range.clone(), x.severity)).collect();
+    assert_eq!(fields, vec![
+        (field::Field::ToCityAirportCode, 33..36, Severity::Warning),
+        (field::Field::OperatingCarrierDesignator, 36..39, Severity::Warning),
+    ]);
+}
+
+#[test]
+fn passenger_names() {
+    let name = PassengerName::from_bcbp("BRUNER/ROMAN MR     ");
+    assert_eq!(name.surname(),     "BRUNER");
+    assert_eq!(name.given_names(), Some("ROMAN"));
+    assert_eq!(name.title(),       Some(Title::Mr));
+    assert!(!name.is_possibly_truncated());
+    assert_eq!(name.to_string(),   "BRUNER/ROMAN MR");
+
+    let name = PassengerName::from_bcbp("DESMARAIS/LUC JEAN  ");
+    assert_eq!(name.given_names(), Some("LUC JEAN"));
+    assert_eq!(name.title(),       None);
+
+    let name = PassengerName::from_bcbp("SMITH/MSTR          ");
+    assert_eq!(name.given_names(), None);
+    assert_eq!(name.title(),       Some(Title::Mstr));
+
+    let name = PassengerName::from_bcbp("WILLIAMS/ALEXANDRA E");
+    assert_eq!(name.given_names(), Some("ALEXANDRA E"));
+    assert!(name.is_possibly_truncated());
+
+    let name = PassengerName::from_bcbp("IVANOVA VASILINA/   ");
+    assert_eq!(name.surname(),     "IVANOVA VASILINA");
+    assert_eq!(name.given_names(), None);
+    assert_eq!(name.to_string(),   "IVANOVA VASILINA");
+
+    let mut bcbp = Bcbp::from(samples::BASE_BCBP).unwrap();
+    assert_eq!(bcbp.passenger_name(), PassengerName::new("BRUNER", Some("ROMAN"), Some(Title::Mr)));
+    assert_eq!(raw::Bcbp::from(samples::BASE_BCBP).unwrap().passenger_name(), bcbp.passenger_name());
+
+    bcbp.set_passenger_name(&PassengerName::new("SOLLE", Some("JOSHUA"), Some(Title::Dr)));
+    assert_eq!(bcbp.name_last,              "SOLLE");
+    assert_eq!(bcbp.name_first.as_deref(),  Some("JOSHUA DR"));
+    assert!(bcbp.build(Mode::Strict).unwrap().starts_with("M1SOLLE/JOSHUA DR     E"));
+}
+
+#[test]
+fn name_transliteration() {
+    assert_eq!(transliterate("Müller-Lüdenscheidt"), "MUELLER LUEDENSCHEIDT");
+    assert_eq!(transliterate("O'Brien"),             "OBRIEN");
+    assert_eq!(transliterate("Straße Øre Åse"),      "STRASSE OERE AASE");
+    assert_eq!(transliterate("Łukasz Żółć"),         "LUKASZ ZOLC");
+    assert_eq!(transliterate("Юлия Ёлкина"),         "IULIIA ELKINA");
+    assert_eq!(transliterate("Γιώργος Θεοδωρίδης"),  "GIORGOS THEODORIDIS");
+
+    let name = PassengerName::new("DESMARAIS", Some("LUC JEAN PIERRE"), Some(Title::Mr));
+    assert_eq!(name.to_bcbp(), "DESMARAIS/LUC J P");
+    let name = PassengerName::new("DESMARAIS", Some("BARTHOLOMEW LUCIEN"), None);
+    assert_eq!(name.to_bcbp(), "DESMARAIS/B L");
+    let name = PassengerName::new("WOLFESCHLEGELSTEINHAUSEN", Some("HUBERT"), Some(Title::Mr));
+    assert_eq!(name.to_bcbp(), "WOLFESCHLEGELSTEINHA");
+
+    let mut bcbp = Bcbp::from(samples::BASE_BCBP).unwrap();
+    bcbp.name_last  = "Müller-Lüdenscheidt".into();
+    bcbp.name_first = Some("José".into());
+    assert_eq!(bcbp.name(), "MUELLER LUEDENSCHEID");
+
+    bcbp.name_last  = "Müller".into();
+    assert_eq!(bcbp.name(), "MUELLER/JOSE");
+    assert!(bcbp.build(Mode::Strict).unwrap().starts_with("M1MUELLER/JOSE        E"));
+
+    // Names which fit the pass are still written with its characters only.
+    bcbp.name_last  = "Bruner".into();
+    bcbp.name_first = Some("Roman Mr".into());
+    assert_eq!(bcbp.name(), "BRUNER/ROMAN MR");
+
+    bcbp.name_last  = "O'Brien".into();
+    bcbp.name_first = Some("Sean".into());
+    assert_eq!(bcbp.name(), "OBRIEN/SEAN");
+    assert!(bcbp.build(Mode::Strict).unwrap().starts_with("M1OBRIEN/SEAN         E"));
+
+    bcbp.name_last  = "ST. JOHN".into();
+    bcbp.name_first = Some("MARY-ANNE".into());
+    assert_eq!(bcbp.name(), "ST JOHN/MARY ANNE");
+}
+
+#[test]
+fn name_matching() {
+    fn with_name(name: &str) -> Bcbp {
+        Bcbp::from(&format!("M1{:<20}EJNUFFX MUCSVOSU 2327 231L013A0052 100", name)).unwrap()
+    }
+
+    let pass = with_name("DESMARAIS/LUC J MR");
+
+    let result = pass.match_name("DESMARAIS/LUC J MR");
+    assert_eq!(result.confidence(), MatchConfidence::Exact);
+    assert!(result.reasons().is_empty());
+
+    let result = pass.match_name("Luc Jean Desmarais");
+    assert_eq!(result.confidence(), MatchConfidence::High);
+    assert_eq!(result.reasons(),    &[MatchReason::TitleIgnored, MatchReason::Initials]);
+
+    let result = pass.match_name("Luc Pierre Jean Desmarais");
+    assert!(result.is_match());
+    assert!(result.reasons().contains(&MatchReason::MiddleNamesMissing));
+
+    let result = pass.match_name("Marc Desmarais");
+    assert_eq!(result.confidence(), MatchConfidence::Low);
+    assert!(result.reasons().contains(&MatchReason::GivenNamesMismatch));
+
+    let result = pass.match_name("Luc Desjardins");
+    assert_eq!(result.confidence(), MatchConfidence::None);
+    assert!(result.reasons().contains(&MatchReason::SurnameMismatch));
+
+    // Cut at 20 characters, the given names are lost.
+    let pass = with_name("WOLFESCHLEGELSTEINHA");
+    let result = pass.match_name("Hubert Wolfeschlegelsteinhausen");
+    assert_eq!(result.confidence(), MatchConfidence::High);
+    assert_eq!(result.reasons(),    &[MatchReason::Truncated]);
+
+    // The given name is cut.
+    let pass = with_name("GARCIA LOPEZ/ALEJAND");
+    let result = pass.match_name("Alejandra García-López");
+    assert_eq!(result.confidence(), MatchConfidence::High);
+    assert_eq!(result.reasons(),    &[MatchReason::Transliterated, MatchReason::Truncated]);
+
+    let pass = with_name("GARCIALOPEZ/MARIA");
+    let result = pass.match_name("GARCIA-LOPEZ/MARIA");
+    assert_eq!(result.confidence(), MatchConfidence::High);
+    assert_eq!(result.reasons(),    &[MatchReason::SeparatorsIgnored]);
+
+    // Transliteration systems disagree on umlauts and Cyrillic vowels.
+    let pass = with_name("MULLER/YULIYA");
+    let result = pass.match_name("Юлия Müller");
+    assert_eq!(result.confidence(), MatchConfidence::High);
+    assert_eq!(result.reasons(),    &[MatchReason::Transliterated, MatchReason::TransliterationVariant]);
+
+    // Names which needed no transliterating only agree as spellings, not as the same person.
+    let pass = with_name("MULLER/IULIIA");
+    let result = pass.match_name("Yuliya Muller");
+    assert_eq!(result.confidence(), MatchConfidence::Low);
+    assert_eq!(result.reasons(),    &[MatchReason::SpellingVariant]);
+
+    for (name, other) in [("SMIT/JOHN", "John Smith"), ("JONES/ANNA", "Anna Yones"), ("BRUNER/ROMAN", "Roman Brunner")] {
+        let result = with_name(name).match_name(other);
+        assert_eq!(result.confidence(), MatchConfidence::Low, "{}", other);
+        assert!(!result.is_match(), "{}", other);
+        assert!(result.reasons().contains(&MatchReason::SpellingVariant), "{}", other);
+    }
+
+    let pass = with_name("MULLER");
+    let result = pass.match_name("Yuliya Muller");
+    assert_eq!(result.confidence(), MatchConfidence::Low);
+    assert_eq!(result.reasons(),    &[MatchReason::GivenNamesMissing]);
+}
+
+#[test]
+fn airline_use() {
+    let registry = AirlineUseRegistry::with_reference_decoders();
+
+    let src = "M1MROZ/MARTIN         EXXXXXX SJCLAXAS 3317 207U001A0006 34D>218 VV8207BAS              2502771980993865 AS AS XXXXX55200000000Z29  00010";
+    let data = registry.decode(&Bcbp::from(src).unwrap().legs[0]).unwrap();
+    assert_eq!(data.get("code"),     Some(&AirlineValue::Text("Z29".into())));
+    assert_eq!(data.get("sequence"), Some(&AirlineValue::Number(10)));
+    assert_eq!(registry.decode_raw(&raw::Bcbp::from(src).unwrap().legs()[0]), Some(data));
+
+    let src = "M1ASKREN/TEST         EA272SL ORDNRTUA 0881 007F002K0303 15C>3180 K6007BUA              2901624760758980 UA UA EY975897            *30600    09  UAG    ";
+    let data = registry.decode(&Bcbp::from(src).unwrap().legs[0]).unwrap();
+    assert_eq!(data.get("format_version"),      Some(&AirlineValue::Number(3)));
+    assert_eq!(data.get("frequent_flyer_tier"), Some(&AirlineValue::Text("UAG".into())));
+    assert_eq!(data.get("data"),                Some(&AirlineValue::Text("0600    09  UAG".into())));
+
+    let src = "M1Mroz/Martin         EXXXXXX YVRYOWAC 0344 211          072>20B0  8203IAC 250140000000000 0AC AC AC000000000     *20000AC 223                14080003068        0B          N";
+    let data = registry.decode_raw(&raw::Bcbp::from(src).unwrap().legs()[0]).unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data.get("format_version"), Some(&AirlineValue::Number(2)));
+    assert_eq!(data.get("frequent_flyer_tier"), None);
+
+    // The 792B example of the second leg, operated by LH, has no Star Alliance data.
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let mut bcbp = Bcbp::from(src).unwrap();
+    assert_eq!(registry.decode(&bcbp.legs[0]), None);
+    assert_eq!(registry.decode(&bcbp.legs[1]), None);
+
+    let mut registry = AirlineUseRegistry::new();
+    registry.register("AC".parse().unwrap(), |data: &str| {
+        let mut ret = AirlineData::new();
+        ret.push("boarding_group", AirlineValue::Char(data.chars().last()?));
+        Some(ret)
+    });
+    assert_eq!(registry.decode(&bcbp.legs[0]).unwrap().get("boarding_group"), Some(&AirlineValue::Char('Z')));
+    assert_eq!(registry.decode(&bcbp.legs[1]), None);
+
+    bcbp.legs[0].var = None;
+    assert_eq!(registry.decode(&bcbp.legs[0]), None);
+}
+
+#[test]
+fn checkin_sequences() {
+    let seq: CheckInSequence = "0052A".parse().unwrap();
+    assert_eq!(seq.number(),    Some(52));
+    assert_eq!(seq.suffix(),    Some('A'));
+    assert_eq!(seq.to_string(), "0052A");
+    assert_eq!("0052 ".parse(), CheckInSequence::new(52, None));
+    assert_eq!("12345".parse(), CheckInSequence::new(12345, None));
+    assert!(CheckInSequence::new(52, None).unwrap() < seq);
+
+    assert_eq!("".parse::<CheckInSequence>().map_err(|e| e.kind()),       Err(ErrorKind::MandatoryDataSize));
+    assert_eq!("12345A".parse::<CheckInSequence>().map_err(|e| e.kind()), Err(ErrorKind::MandatoryDataSize));
+    assert_eq!("00X2".parse::<CheckInSequence>().map_err(|e| e.kind()),   Err(ErrorKind::DigitsExpected));
+    assert_eq!(CheckInSequence::new(100_000, None).map_err(|e| e.kind()),   Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(CheckInSequence::new(1, Some('1')).map_err(|e| e.kind()),    Err(ErrorKind::AlphaExpected));
+    assert_eq!(CheckInSequence::other("INF  "), Ok(CheckInSequence::Other("INF".into())));
+    assert_eq!(CheckInSequence::other("INFANT").map_err(|e| e.kind()),     Err(ErrorKind::MandatoryDataSize));
+
+    for src in [
+        "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052A100",
+        "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A12345100",
+        "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A     100",
+    ] {
+        let bcbp = Bcbp::from(src).unwrap();
+        assert_eq!(bcbp.build(Mode::Tolerant).unwrap(), src);
+    }
+
+    let bcbp = Bcbp::from("M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052A100").unwrap();
+    assert_eq!(bcbp.legs[0].sequence, Some(seq));
+
+    // Values defined by the host system are kept as they are, but not in `Mode::Strict`.
+    for (src, other) in [
+        ("M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013AINF  100", "INF"),
+        ("M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A00-2 100", "00-2"),
+    ] {
+        let bcbp = Bcbp::from(src).unwrap();
+        assert_eq!(bcbp.legs[0].sequence, Some(CheckInSequence::Other(other.into())));
+        assert_eq!(bcbp.legs[0].sequence.as_ref().unwrap().number(), None);
+        assert_eq!(bcbp.build(Mode::Tolerant).unwrap(), src);
+        assert!(bcbp.build(Mode::Strict).is_err());
+        assert_eq!(Bcbp::from_with_mode(src, Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::DigitsExpected));
+
+        let (_, diagnostics) = Bcbp::diagnose(src);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].field,    field::Field::CheckInSequenceNumber);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].range,    52..57);
+    }
+}
+
+#[test]
+fn ticket_numbers() {
+    let ticket = TicketNumber::new(14, 1234567890).unwrap();
+    assert_eq!("0141234567890".parse(),  Ok(ticket));
+    assert_eq!("014-1234567890".parse(), Ok(ticket));
+    assert_eq!(ticket.to_string(),       "0141234567890");
+    assert_eq!(format!("{:#}", ticket),  "014-1234567890");
+    assert_eq!(ticket.airline(),         14);
+    assert_eq!(ticket.serial(),          1234567890);
+    assert_eq!(ticket.check_digit(),     3);
+    assert!(!ticket.verify_check_digit(0));
+
+    assert_eq!(TicketNumber::new(1000, 1).map_err(|e| e.kind()),              Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(TicketNumber::new(14, 10_000_000_000).map_err(|e| e.kind()),   Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(TicketNumber::from_parts("014", "123456789").map_err(|e| e.kind()), Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(TicketNumber::from_parts("01A", "1234567890").map_err(|e| e.kind()), Err(ErrorKind::DigitsExpected));
+    assert_eq!("014123456789".parse::<TicketNumber>().map_err(|e| e.kind()),  Err(ErrorKind::MandatoryDataSize));
+
+    let conjunction: Vec<TicketNumber> = "0141234567898".parse::<TicketNumber>().unwrap().conjunction("01").unwrap().collect();
+    assert_eq!(conjunction.len(), 4);
+    assert_eq!(conjunction[3].to_string(), "0141234567901");
+    assert_eq!(ticket.conjunction("0").unwrap().count(), 1);
+    assert_eq!(ticket.conjunction("89").map(|x| x.count()), Ok(100));
+    assert_eq!(TicketNumber::new(14, 9_999_999_999).unwrap().conjunction("0").map(|x| x.count()).map_err(|e| e.kind()), Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(ticket.conjunction("9X").map(|x| x.count()).map_err(|e| e.kind()), Err(ErrorKind::DigitsExpected));
+
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let mut bcbp = Bcbp::from(src).unwrap();
+    assert_eq!(bcbp.legs[0].ticket_number(), Some(ticket));
+    assert_eq!(bcbp.legs[1].ticket_number(), "0140987654321".parse().ok());
+
+    let raw = raw::Bcbp::from(src).unwrap();
+    assert_eq!(raw.legs()[0].ticket_number(), Some(ticket));
+
+    bcbp.legs[1].set_ticket_number(&"220-0000000042".parse().unwrap());
+    assert!(bcbp.build(Mode::Strict).unwrap().contains("2A2200000000042 1AC"));
+
+    // The airline numeric code used to be read as 0 when it was not made of digits.
+    let src = src.replacen("2A0141234567890", "2A01X1234567890", 1);
+    assert_eq!(Bcbp::from(&src).map_err(|e| (e.kind(), e.field())), Err((ErrorKind::DigitsExpected, Some(field::Field::AirlineNumericCode))));
+}
+
+#[test]
+fn version_schema() {
+    // A version 1 pass, whose repeated section is followed by the ID/AD indicator of version 2.
+    let src = "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 32F>103  W2607424970676211                       2";
+
+    let bcbp = Bcbp::from(src).unwrap();
+    assert_eq!(bcbp.version,                   Some(BcbpVersion::V1));
+    assert_eq!(bcbp.legs[0].selectee(),        SelecteeIndicator::Selectee);
+    assert_eq!(bcbp.legs[0].id_ad(),           IdAdIndicator::None);
+    assert_eq!(bcbp.legs[0].unexpected_data.as_deref(), Some("2"));
+    assert_eq!(bcbp.legs[0].var,               None);
+    assert_eq!(bcbp.build(Mode::Tolerant).unwrap(), src);
+    assert_eq!(Bcbp::from_with_mode(src, Mode::Strict).map_err(|e| e.kind()), Err(ErrorKind::CoditionalDataSize));
+
+    let raw = raw::Bcbp::from(src).unwrap();
+    assert_eq!(raw.version(),                            Some(BcbpVersion::V1));
+    assert_eq!(raw.legs()[0].id_ad_indicator(),          None);
+    assert_eq!(raw.legs()[0].unexpected_data(),          Some("2"));
+    assert_eq!(raw.legs()[0].airline_individual_use(),   None);
+
+    // The same data is the ID/AD indicator of a version 5 pass.
+    let src = src.replace(">1", ">5");
+    let mut bcbp = Bcbp::from(&src).unwrap();
+    assert_eq!(bcbp.legs[0].id_ad(),           IdAdIndicator::Idb1PositiveSpace);
+    assert_eq!(bcbp.legs[0].unexpected_data,   None);
+
+    // Fields a version does not define are left out, in strict mode too.
+    bcbp.version = Some(BcbpVersion::V1);
+    let built = "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 317>103  W0E07424970676211";
+    assert_eq!(bcbp.build(Mode::Tolerant).unwrap(), built);
+    assert_eq!(bcbp.build(Mode::Strict).unwrap(),   built);
+
+    // Unknown versions keep the layout of the latest one.
+    assert_eq!(BcbpVersion::from_char('9'), BcbpVersion::Other('9'));
+    assert_eq!(BcbpVersion::Other('9').repeated_fields(), BcbpVersion::LATEST.repeated_fields());
+    assert!(!BcbpVersion::V4.defines(field::Field::FastTrack));
+    assert!(BcbpVersion::V5.defines(field::Field::FastTrack));
+}
+
+#[test]
+fn legacy_pass() {
+    let src = "M1SOLLE/JOSUHUA       EQHSLJX ATLMEMDL 0254 006Y28C      10C3JIJI7O4M28C";
+
+    let bcbp = Bcbp::from(src).unwrap();
+    assert_eq!(bcbp.version,                 Some(BcbpVersion::Legacy));
+    assert_eq!(bcbp.name(),                  "SOLLE/JOSUHUA");
+    assert_eq!(bcbp.legs[0].seat(),          Some(&Seat::Assigned { row: 28, letter: 'C' }));
+    assert_eq!(bcbp.legs[0].sequence,        None);
+    assert_eq!(bcbp.legs[0].var.as_deref(),  Some("3JIJI7O4M28C"));
+    assert_eq!(Bcbp::from_with_mode(src, Mode::Strict).as_ref(), Ok(&bcbp));
+
+    let built = bcbp.build(Mode::Tolerant).unwrap();
+    assert_eq!(built, "M1SOLLE/JOSUHUA       EQHSLJX ATLMEMDL 0254 006Y028C     10C3JIJI7O4M28C");
+    assert_eq!(Bcbp::from(&built), Ok(bcbp));
+
+    // A marker other than the version one is still rejected.
+    let src = "M1SOLLE/JOSUHUA       EQHSLJX ATLMEMDL 0254 006Y28C      10C+JIJI7O4M28C";
+    let err = Bcbp::from(src).unwrap_err();
+    assert_eq!(err.kind(),   ErrorKind::InvalidPrefix('+'));
+    assert_eq!(err.field(),  Some(field::Field::BeginningOfVersionNumber));
+    assert_eq!(err.offset(), Some(60));
+
+    // So is a letter in place of the marker of an otherwise versioned section.
+    let src = "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 104X600";
+    let err = Bcbp::from(src).unwrap_err();
+    assert_eq!(err.kind(),   ErrorKind::InvalidPrefix('X'));
+    assert_eq!(err.offset(), Some(60));
+    assert_eq!(raw::Bcbp::from(src).map_err(|e| e.kind()), Err(ErrorKind::InvalidPrefix('X')));
+
+    // A pass declaring the version number 0 is not a legacy one.
+    let src = "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 104>000";
+    let bcbp = Bcbp::from(src).unwrap();
+    assert_eq!(bcbp.version, Some(BcbpVersion::Other('0')));
+    assert_eq!(bcbp.build(Mode::Tolerant).unwrap(), src);
+    assert_eq!(raw::Bcbp::from(src).unwrap().to_string(), src);
+}
+
+#[test]
+fn diagnose() {
+    use field::Field;
+
+    type Summary = (Field, Option<usize>, std::ops::Range<usize>, Severity, ErrorKind);
+
+    fn summary(diagnostics: &[Diagnostic]) -> Vec<Summary> {
+        diagnostics.iter().map(|d| (d.field, d.leg, d.range.clone(), d.severity, d.error.kind())).collect()
+    }
+
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let (bcbp, diagnostics) = Bcbp::diagnose(src);
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(Bcbp::from_with_mode(src, Mode::Strict), Ok(bcbp));
+
+    // Lowercase destination, letters in the baggage tag and day 0 for the second flight.
+    let src = "M2DESMARAIS/LUC       EABC123 YULfraAC 0834 226F001A0025 14D>6181WW6225BAC 00141234X60032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 000C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let (bcbp, diagnostics) = Bcbp::diagnose(src);
+    assert_eq!(summary(&diagnostics), vec![
+        (Field::ToCityAirportCode, Some(0), 33..36, Severity::Warning, ErrorKind::AlphaExpected),
+        (Field::BaggageTagLicensePlateNumbers, None, 75..88, Severity::Error, ErrorKind::DigitsExpected),
+        (Field::DateOfFlight, Some(1), 158..161, Severity::Error, ErrorKind::InvalidDate),
+    ]);
+    assert_eq!(Bcbp::from(src).map_err(|e| e.kind()), Err(ErrorKind::DigitsExpected));
+
+    let error = &diagnostics[2].error;
+    assert_eq!(error.offset(), Some(158));
+    assert_eq!(error.leg(),    Some(1));
+    assert!(std::error::Error::source(error).is_some());
+
+    assert_eq!(bcbp.legs.len(),              2);
+    assert_eq!(bcbp.legs[0].dst_airport().unwrap(),   "fra");
+    assert_eq!(bcbp.bagtag1(),               None);
+    assert_eq!(bcbp.boardingpass_airline().unwrap(), "AC");
+    assert_eq!(bcbp.legs[1].flight_day,      None);
+    assert_eq!(bcbp.legs[1].airline().unwrap(),   "LH");
+    assert_eq!(bcbp.legs[1].var.as_deref(),  Some("WQ"));
+
+    // Parsing stops where the input ends, keeping what was read.
+    let (bcbp, diagnostics) = Bcbp::diagnose("M1BRUNER/ROMAN MR     EJNUFFX MUC\u{e9}VOSU 23");
+    assert_eq!(summary(&diagnostics), vec![
+        (Field::ToCityAirportCode, Some(0), 33..36, Severity::Error, ErrorKind::InvalidCharacters),
+        (Field::FlightNumber, Some(0), 39..42, Severity::Error, ErrorKind::UnexpectedEndOfInput),
+    ]);
+    assert_eq!(bcbp.name(),                "BRUNER/ROMAN MR");
+    assert_eq!(bcbp.legs[0].src_airport().unwrap(), "MUC");
+    assert_eq!(bcbp.legs[0].airline().unwrap(), "OSU");
+}
+
+#[test]
+fn bag_tags() {
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let mut bcbp = Bcbp::from(src).unwrap();
+
+    let range = *bcbp.bagtag1().unwrap();
+    assert_eq!(range.kind(),    BagTagKind::Interline);
+    assert_eq!(range.carrier(), 14);
+    assert_eq!(range.serial(),  123456);
+    assert_eq!(range.count(),   3);
+    assert_eq!(range.to_string(), "0014123456003");
+    assert_eq!(
+        range.tags().collect::<Vec<_>>(),
+        vec!["0014123456", "0014123457", "0014123458"],
+    );
+    assert!(range.contains("0014123458"));
+    assert!(!range.contains("0014123459"));
+
+    assert_eq!(bcbp.bagtag2(), None);
+    assert_eq!(bcbp.bagtag3(), None);
+
+    bcbp.set_bagtag3("2220999999001").unwrap();
+    let built = bcbp.build(Mode::Tolerant).unwrap();
+    let parsed = Bcbp::from(&built).unwrap();
+
+    assert_eq!(parsed.bagtag1(), bcbp.bagtag1());
+    assert_eq!(parsed.bagtag2(), None);
+    assert_eq!(parsed.bagtag3().unwrap().kind(), BagTagKind::InterlineRush);
+    assert_eq!(parsed.bagtag3().unwrap().tags().collect::<Vec<_>>(), vec!["2220999999"]);
+
+    // Before version 6 a count of 000 is a single bag.
+    let v5 = src.replace(">6181WW6225BAC 0014123456003", ">5181WW6225BAC 0014123456000");
+    let range = *Bcbp::from(&v5).unwrap().bagtag1().unwrap();
+    assert_eq!(range.count(), 0);
+    assert_eq!(range.tags().collect::<Vec<_>>(), vec!["0014123456"]);
+    assert!(range.contains("0014123456"));
+    assert!(!range.contains("0014123457"));
+
+    assert_eq!(bcbp.set_bagtag1("001412345600").map_err(|e| e.kind()), Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(bcbp.set_bagtag1("00141234560X3").map_err(|e| e.kind()), Err(ErrorKind::DigitsExpected));
+    assert_eq!(bcbp.set_bagtag1("0014999999002").map_err(|e| e.kind()), Err(ErrorKind::MandatoryDataSize));
+    assert_eq!(BagTagRange::new(BagTagKind::Other('X'), 14, 1, 1).map_err(|e| e.kind()), Err(ErrorKind::DigitsExpected));
+}
+
+#[test]
+fn from_raw() {
+    let src = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ";
+    let raw = raw::Bcbp::from(src).unwrap();
+    assert_eq!(raw.version(),                           Some(BcbpVersion::V6));
+    assert_eq!(raw.baggage_tag_license_plate_numbers(), Some("0014123456003"));
+    assert_eq!(raw.legs()[1].compartment(),             'C');
+    assert_eq!(Bcbp::try_from(raw).unwrap(),            Bcbp::from(src).unwrap());
+
+    // Letters in the baggage tag are only rejected by the owned model, at the position of the item.
+    let src = src.replace("0014123456003", "00141234X6003");
+    let raw = raw::Bcbp::from(&src).unwrap();
+    assert_eq!(raw.baggage_tag_license_plate_numbers(), Some("00141234X6003"));
+
+    let err = Bcbp::try_from(raw).unwrap_err();
+    assert_eq!(err.kind(),   ErrorKind::DigitsExpected);
+    assert_eq!(err.field(),  Some(field::Field::BaggageTagLicensePlateNumbers));
+    assert_eq!(err.offset(), Some(75));
+    assert_eq!(err.leg(),    None);
+
+    // Items keep their position once replaced, or copied out of the input.
+    let mut raw = raw::Bcbp::from(&src).unwrap().into_owned();
+    raw.legs_mut()[1].set_flight_day("000").unwrap();
+    raw.set_baggage_tag_license_plate_numbers("0014123456003").unwrap();
+
+    let err = Bcbp::try_from(raw).unwrap_err();
+    assert_eq!(err.kind(),   ErrorKind::InvalidDate);
+    assert_eq!(err.field(),  Some(field::Field::DateOfFlight));
+    assert_eq!(err.offset(), Some(158));
+    assert_eq!(err.leg(),    Some(1));
+
+    // Both read the same grammar.
+    let src = "M0BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100";
+    assert_eq!(raw::Bcbp::from(src).map_err(|e| e.kind()), Err(ErrorKind::InvalidLegsCount));
+    assert_eq!(Bcbp::from(src).map_err(|e| e.kind()),      Err(ErrorKind::InvalidLegsCount));
+}
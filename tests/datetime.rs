@@ -71,7 +71,7 @@ fn test_day_of_year_valid() {
 
 #[test]
 fn test_day_of_year_invalid() {
-    for day in std::iter::once(0).chain((DAYS_IN_YEAR + 2)..=std::u16::MAX) {
+    for day in std::iter::once(0).chain((DAYS_IN_YEAR + 2)..=u16::MAX) {
         assert_eq!(
             DayOfYear::new(day),
             Err(Error::InvalidDayOfYearRange(day)),
@@ -169,10 +169,78 @@ fn test_day_of_year_to_date_adapt() {
     }
 }
 
+#[test]
+fn test_julian_issue_date() {
+    use time::Date;
+
+    for year in MIN_YEAR..MAX_YEAR {
+        let for_date = Date::from_ordinal_date(year, 200).unwrap();
+
+        for back in 0..10 {
+            let issued_year = year - back;
+            let digit = issued_year.rem_euclid(10) as u8;
+
+            for day in [1, 100, 200] {
+                let issued = JulianIssueDate::from_str(&format!("{digit}{day:03}")).unwrap();
+
+                assert_eq!(issued.year_digit(), digit);
+                assert_eq!(issued.day().ordinal(), day);
+                assert_eq!(issued.to_string(), format!("{digit}{day:03}"));
+
+                if back == 0 && day > 200 {
+                    continue;
+                }
+
+                assert_eq!(
+                    issued.to_date_adapt(for_date),
+                    Ok(Date::from_ordinal_date(issued_year, day).unwrap())
+                );
+            }
+        }
+
+        // A day later is allowed for, as the pass may be issued in a time zone ahead.
+        let digit = year.rem_euclid(10) as u8;
+        let issued = JulianIssueDate::new(digit, DayOfYear::new(201).unwrap()).unwrap();
+        assert_eq!(issued.to_date_adapt(for_date), Ok(Date::from_ordinal_date(year, 201).unwrap()));
+
+        // Later the same year is in the future.
+        let issued = JulianIssueDate::new(digit, DayOfYear::new(202).unwrap()).unwrap();
+        assert_eq!(
+            issued.to_date_adapt(for_date),
+            Err(Error::DateInFuture(Date::from_ordinal_date(year, 202).unwrap()))
+        );
+
+        let issued = JulianIssueDate::new(digit, DayOfYear::new(DAYS_IN_YEAR + 1).unwrap()).unwrap();
+        let for_date = Date::from_ordinal_date(year, DAYS_IN_YEAR).unwrap();
+        if is_leap_year(year) {
+            assert_eq!(issued.to_date_adapt(for_date), Ok(Date::from_ordinal_date(year, DAYS_IN_YEAR + 1).unwrap()));
+        } else {
+            assert_eq!(issued.to_date_adapt(for_date), Err(Error::OverflowNotLeapYear));
+        }
+
+        // The first day of the next year, on the last day of this one.
+        let last = Date::from_ordinal_date(year, time::util::days_in_year(year)).unwrap();
+        let issued = JulianIssueDate::new((year + 1).rem_euclid(10) as u8, DayOfYear::new(1).unwrap()).unwrap();
+        assert_eq!(issued.to_date_adapt(last), Ok(Date::from_ordinal_date(year + 1, 1).unwrap()));
+    }
+}
+
+#[test]
+fn test_julian_issue_date_invalid() {
+    assert_eq!(JulianIssueDate::new(10, DayOfYear::new(1).unwrap()), Err(Error::InvalidYearDigit(10)));
+
+    for s in ["", "601", "60012", "6O12", " 012", "    "] {
+        assert_eq!(JulianIssueDate::from_str(s), Err(Error::InvalidInput(s.to_owned())));
+    }
+
+    assert_eq!(JulianIssueDate::from_str("6000"), Err(Error::InvalidDayOfYearRange(0)));
+    assert_eq!(JulianIssueDate::from_str("6367"), Err(Error::InvalidDayOfYearRange(367)));
+}
+
 #[test]
 fn test_tz_as_str() {
-    assert_eq!(TzTag::Local.as_str().as_deref(), Some("L"));
-    assert_eq!(TzTag::Utc.as_str().as_deref(), Some("Z"));
+    assert_eq!(TzTag::Local.as_str(), Some("L"));
+    assert_eq!(TzTag::Utc.as_str(), Some("Z"));
     assert_eq!(TzTag::None.as_str(), None);
 }
 
@@ -181,7 +249,7 @@ fn test_short_date() {
     for (month, len) in MONTH_LENS {
         for day in 1..=len {
             let short_date = ShortDate::new(month, day)
-                .expect(&format!("{day} must be a valid of day of {}", month.as_str()));
+                .unwrap_or_else(|_| panic!("{day} must be a valid of day of {}", month.as_str()));
 
             assert_eq!(short_date.day(), day);
             assert_eq!(short_date.month(), month);
@@ -277,7 +345,7 @@ fn test_short_date_to_string_from_string() {
 
 #[test]
 fn test_short_date_bad_strs() {
-    const MAX_SAMPLE_LEN: usize = 1_000_00;
+    const MAX_SAMPLE_LEN: usize = 100_000;
     use rand::{distr::{Alphanumeric, SampleString}};
     let mut rng = rand::rng();
 
@@ -294,7 +362,7 @@ fn test_short_date_bad_strs() {
 
 #[test]
 fn test_time_bad_strs() {
-    const MAX_SAMPLE_LEN: usize = 1_000_00;
+    const MAX_SAMPLE_LEN: usize = 100_000;
     use rand::{distr::{Alphanumeric, SampleString}};
     let mut rng = rand::rng();
 
@@ -6,7 +6,7 @@
 //! Synthetic test cases with invalid boarding pass data.
 
 use iata::bcbp::{
-    Error,
+    ErrorKind,
     field::Field,
     raw::*
 };
@@ -15,67 +15,101 @@ use iata::bcbp::{
 fn trailing_characters() {
     // This is a complete and valid Type 'M' boarding pass from the IATA 792B examples, with a trailing '+'.
     const PASS_STR: &str = "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100^100+";
-    assert_eq!(Bcbp::from(PASS_STR), Err(Error::TrailingData));
+    let error = Bcbp::from(PASS_STR).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::TrailingData);
+    assert_eq!(error.offset(), Some(64));
+    assert_eq!(error.to_string(), "TRAILING_DATA in Security Data at offset 64");
 }
 
 #[test]
 fn unsupported_format() {
     // The first character indicates the format. This is a valid Type 'M' boarding pass from the IATA 792B examples, with the wrong format code.
     const PASS_STR_S: &str = "S1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100^100";
-    assert_eq!(Bcbp::from(PASS_STR_S), Err(Error::InvalidFormatCode('S')));
+    let error = Bcbp::from(PASS_STR_S).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::InvalidFormatCode('S'));
+    assert_eq!(error.field(),  Some(Field::FormatCode));
+    assert_eq!(error.offset(), Some(0));
 
     const PASS_STR_LITTLE_M: &str = "m1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100^100";
-    assert_eq!(Bcbp::from(PASS_STR_LITTLE_M), Err(Error::InvalidFormatCode('m')));
+    let error = Bcbp::from(PASS_STR_LITTLE_M).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::InvalidFormatCode('m'));
 }
 
 #[test]
 fn invalid_characters() {
     // This is a complete and valid Type 'M' boarding pass from the IATA 792B examples, with a non-ASCII character.
     const PASS_STR: &str = "M1DESMARAIS/LUç       EABC123 YULFRAAC 0834 326J001A0025 100^100";
-    assert_eq!(Bcbp::from(PASS_STR), Err(Error::InvalidCharacters));
+    let error = Bcbp::from(PASS_STR).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::InvalidCharacters);
+    assert_eq!(error.offset(), Some(14));
     const PASS_STR_MINIMAL: &str = "ç";
-    assert_eq!(Bcbp::from(PASS_STR_MINIMAL), Err(Error::InvalidCharacters));
+    let error = Bcbp::from(PASS_STR_MINIMAL).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::InvalidCharacters);
+    assert_eq!(error.offset(), Some(0));
 }
 
 #[test]
 fn invalid_start_of_security_data() {
     // This is a complete and valid Type 'M' boarding pass from the IATA 792B examples, using a '+' instead of '^' for start of security data.
     const PASS_STR: &str = "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100+100";
-    assert_eq!(Bcbp::from(PASS_STR), Err(Error::InvalidPrefix(Field::BeginningOfSecurityData, '+')));
+    let error = Bcbp::from(PASS_STR).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::InvalidPrefix('+'));
+    assert_eq!(error.field(),  Some(Field::BeginningOfSecurityData));
+    assert_eq!(error.offset(), Some(60));
+    assert_eq!(error.leg(),    None);
 }
 
 #[test]
 fn invalid_start_of_version_number() {
     // This is a complete and valid Type 'M' boarding pass from the IATA 792B examples, using a '+' instead of '>' for start of version number.
     const PASS_STR: &str = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D+6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ^100";
-    assert_eq!(Bcbp::from(PASS_STR), Err(Error::InvalidPrefix(Field::BeginningOfVersionNumber, '+')));
+    let error = Bcbp::from(PASS_STR).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::InvalidPrefix('+'));
+    assert_eq!(error.field(),  Some(Field::BeginningOfVersionNumber));
+    assert_eq!(error.offset(), Some(60));
 }
 
 #[test]
 fn expected_integer() {
     // This is a complete and valid Type 'M' boarding pass from the IATA 792B examples, with leg count 'X'.
     const PASS_STR_1: &str = "MXDESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100^100+";
-    assert_eq!(Bcbp::from(PASS_STR_1), Err(Error::ExpectedInteger(Field::NumberOfLegsEncoded)));
+    let error = Bcbp::from(PASS_STR_1).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::ExpectedInteger);
+    assert_eq!(error.field(),  Some(Field::NumberOfLegsEncoded));
+    assert_eq!(error.offset(), Some(1));
 
     // This is a complete and valid Type 'M' boarding pass from the IATA 792B examples, with security data length 'YY'.
     const PASS_STR_2: &str = "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100^1YY";
-    assert_eq!(Bcbp::from(PASS_STR_2), Err(Error::ExpectedInteger(Field::LengthOfSecurityData)));
+    let error = Bcbp::from(PASS_STR_2).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::ExpectedInteger);
+    assert_eq!(error.field(),  Some(Field::LengthOfSecurityData));
+    assert_eq!(error.offset(), Some(62));
 }
 
 #[test]
 fn subsection_too_long() {
     // This is a complete and valid Type 'M' boarding pass from the IATA 792B examples, with an 'FF' long conditional.
     const PASS_STR: &str = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 1FF>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ^100";
-    assert_eq!(Bcbp::from(PASS_STR), Err(Error::SubsectionTooLong));
+    let error = Bcbp::from(PASS_STR).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::SubsectionTooLong);
+    assert_eq!(error.field(),  Some(Field::FieldSizeOfVariableSizeField));
+    assert_eq!(error.offset(), Some(58));
+    assert_eq!(error.leg(),    Some(0));
 }
 
 #[test]
 fn unexpected_end_of_input() {
     // This is a complete and valid Type 'M' boarding pass from the IATA 792B examples, with a security data extending past end of input.
     const PASS_STR_SEC: &str = "M2DESMARAIS/LUC       EABC123 YULFRAAC 0834 226F001A0025 14D>6181WW6225BAC 00141234560032A0141234567890 1AC AC 1234567890123    20KYLX58ZDEF456 FRAGVALH 3664 227C012C0002 12E2A0140987654321 1AC AC 1234567890123    2PCNWQ^101";
-    assert_eq!(Bcbp::from(PASS_STR_SEC), Err(Error::UnexpectedEndOfInput(Field::SecurityData)));
+    let error = Bcbp::from(PASS_STR_SEC).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::UnexpectedEndOfInput);
+    assert_eq!(error.field(),  Some(Field::SecurityData));
+    assert_eq!(error.offset(), Some(224));
 
     // This is an incomplete type M pass truncated half way through the name field.
     const PASS_STR_NAME: &str = "M2DESMARAIS";
-    assert_eq!(Bcbp::from(PASS_STR_NAME), Err(Error::UnexpectedEndOfInput(Field::PassengerName)));
+    let error = Bcbp::from(PASS_STR_NAME).unwrap_err();
+    assert_eq!(error.kind(),   ErrorKind::UnexpectedEndOfInput);
+    assert_eq!(error.field(),  Some(Field::PassengerName));
+    assert_eq!(error.offset(), Some(2));
 }
@@ -6,6 +6,20 @@
 //! Test cases derived from real-world boarding pass data.
 
 use iata::bcbp::raw::*;
+use iata::bcbp::{
+    field::Field,
+    BcbpVersion,
+    BoardingPassSource,
+    CheckInSource,
+    DocumentType,
+    DocumentVerification,
+    ErrorKind,
+    PaxStatus,
+    PaxType,
+    SelecteeIndicator,
+    TicketIndicator,
+};
+use time::macros::date;
 
 
 #[test]
@@ -13,14 +27,15 @@ fn alaska_boarding_pass() {
     const PASS_STR: &str = "M1MROZ/MARTIN         EXXXXXX SJCLAXAS 3317 207U001A0006 34D>218 VV8207BAS              2502771980993865 AS AS XXXXX55200000000Z29  00010";
     let pass_data = Bcbp::from(PASS_STR).unwrap();
     assert_eq!(pass_data.pax_name(), "MROZ/MARTIN         ");
-    assert_eq!(pass_data.eticket_flag(), 'E');
+    assert_eq!(pass_data.eticket_flag(), TicketIndicator::ElectronicTicket);
     assert_eq!(pass_data.legs().len(), 1);
 
-    assert_eq!(pass_data.pax_description(), Some(' '));
-    assert_eq!(pass_data.source_of_check_in(), Some('V'));
-    assert_eq!(pass_data.source_of_boarding_pass_issuance(), Some('V'));
+    assert_eq!(pass_data.pax_description(), Some(PaxType::None));
+    assert_eq!(pass_data.source_of_check_in(), Some(CheckInSource::ThirdPartyVendor));
+    assert_eq!(pass_data.source_of_boarding_pass_issuance(), Some(BoardingPassSource::ThirdPartyVendor));
     assert_eq!(pass_data.date_of_issue_of_boarding_pass(), Some("8207"));
-    assert_eq!(pass_data.doc_type(), Some('B'));
+    assert_eq!(pass_data.issue_date(date!(2018 - 08 - 01)), Ok(Some(date!(2018 - 07 - 26))));
+    assert_eq!(pass_data.doc_type(), Some(DocumentType::BoardingPass));
     assert_eq!(pass_data.airline_designator_of_boarding_pass_issuer(), Some("AS "));
     assert_eq!(pass_data.baggage_tag_license_plate_numbers(), Some("             "));
     assert_eq!(pass_data.first_non_consecutive_baggage_tag_license_plate_numbers(), None);
@@ -37,12 +52,12 @@ fn alaska_boarding_pass() {
         assert_eq!(first_leg.compartment(), 'U');
         assert_eq!(first_leg.seat(), "001A");
         assert_eq!(first_leg.checkin_sequence(), "0006 ");
-        assert_eq!(first_leg.pax_status(), '3');
+        assert_eq!(first_leg.pax_status(), PaxStatus::Other('3'));
 
         assert_eq!(first_leg.airline_numeric_code(), Some("027"));
         assert_eq!(first_leg.document_form_serial_number(), Some("7198099386"));
-        assert_eq!(first_leg.selectee_indicator(), Some('5'));
-        assert_eq!(first_leg.international_document_verification(), Some(' '));
+        assert_eq!(first_leg.selectee_indicator(), Some(SelecteeIndicator::Other('5')));
+        assert_eq!(first_leg.international_document_verification(), Some(DocumentVerification::None));
         assert_eq!(first_leg.marketing_carrier_designator(), Some("AS "));
         assert_eq!(first_leg.frequent_flyer_airline(), Some("AS "));
         assert_eq!(first_leg.frequent_flyer_number(), Some("XXXXX55200000000"));
@@ -58,14 +73,14 @@ fn air_canada_boarding_pass() {
     const PASS_STR: &str = "M1Mroz/Martin         EXXXXXX YVRYOWAC 0344 211          072>20B0  8203IAC 250140000000000 0AC AC AC000000000     *20000AC 223                14080003068        0B          N";
     let pass_data = Bcbp::from(PASS_STR).unwrap();
     assert_eq!(pass_data.pax_name(), "Mroz/Martin         ");
-    assert_eq!(pass_data.eticket_flag(), 'E');
+    assert_eq!(pass_data.eticket_flag(), TicketIndicator::ElectronicTicket);
     assert_eq!(pass_data.legs().len(), 1);
 
-    assert_eq!(pass_data.pax_description(), Some('0'));
-    assert_eq!(pass_data.source_of_check_in(), Some(' '));
-    assert_eq!(pass_data.source_of_boarding_pass_issuance(), Some(' '));
+    assert_eq!(pass_data.pax_description(), Some(PaxType::Adult));
+    assert_eq!(pass_data.source_of_check_in(), Some(CheckInSource::None));
+    assert_eq!(pass_data.source_of_boarding_pass_issuance(), Some(BoardingPassSource::None));
     assert_eq!(pass_data.date_of_issue_of_boarding_pass(), Some("8203"));
-    assert_eq!(pass_data.doc_type(), Some('I'));
+    assert_eq!(pass_data.doc_type(), Some(DocumentType::ItineraryReceipt));
     assert_eq!(pass_data.airline_designator_of_boarding_pass_issuer(), Some("AC "));
     assert_eq!(pass_data.baggage_tag_license_plate_numbers(), None);
     assert_eq!(pass_data.first_non_consecutive_baggage_tag_license_plate_numbers(), None);
@@ -82,12 +97,12 @@ fn air_canada_boarding_pass() {
         assert_eq!(first_leg.compartment(), ' ');
         assert_eq!(first_leg.seat(), "    ");
         assert_eq!(first_leg.checkin_sequence(), "     ");
-        assert_eq!(first_leg.pax_status(), '0');
+        assert_eq!(first_leg.pax_status(), PaxStatus::NotCheckedIn);
 
         assert_eq!(first_leg.airline_numeric_code(), Some("014"));
         assert_eq!(first_leg.document_form_serial_number(), Some("0000000000"));
-        assert_eq!(first_leg.selectee_indicator(), Some(' '));
-        assert_eq!(first_leg.international_document_verification(), Some('0'));
+        assert_eq!(first_leg.selectee_indicator(), Some(SelecteeIndicator::None));
+        assert_eq!(first_leg.international_document_verification(), Some(DocumentVerification::NotRequired));
         assert_eq!(first_leg.marketing_carrier_designator(), Some("AC "));
         assert_eq!(first_leg.frequent_flyer_airline(), Some("AC "));
         assert_eq!(first_leg.frequent_flyer_number(), Some("AC000000000     "));
@@ -97,3 +112,77 @@ fn air_canada_boarding_pass() {
         assert_eq!(first_leg.airline_individual_use(), Some("*20000AC 223                14080003068        0B          N"));
     }
 }
+
+#[test]
+fn legacy_boarding_pass() {
+    // An old, M.0 or M.1 boarding pass issued before the version number was introduced.
+    const PASS_STR: &str = "M1SOLLE/JOSUHUA       EQHSLJX ATLMEMDL 0254 006Y28C      10C3JIJI7O4M28C";
+    let pass_data = Bcbp::from(PASS_STR).unwrap();
+    assert_eq!(pass_data.pax_name(), "SOLLE/JOSUHUA       ");
+    assert_eq!(pass_data.eticket_flag(), TicketIndicator::ElectronicTicket);
+    assert_eq!(pass_data.legs().len(), 1);
+    assert_eq!(pass_data.version(), Some(BcbpVersion::Legacy));
+
+    assert_eq!(pass_data.pax_description(), None);
+    assert_eq!(pass_data.date_of_issue_of_boarding_pass(), None);
+
+    { // Fields in leg 1 of 1.
+        let first_leg = &pass_data.legs()[0];
+        assert_eq!(first_leg.pnr(), "QHSLJX ");
+        assert_eq!(first_leg.src_airport(), "ATL");
+        assert_eq!(first_leg.dst_airport(), "MEM");
+        assert_eq!(first_leg.airline(), "DL ");
+        assert_eq!(first_leg.flight_number(), "0254 ");
+        assert_eq!(first_leg.flight_day(), "006");
+        assert_eq!(first_leg.compartment(), 'Y');
+        assert_eq!(first_leg.seat(), "28C ");
+        assert_eq!(first_leg.checkin_sequence(), "     ");
+        assert_eq!(first_leg.pax_status(), PaxStatus::CheckedIn);
+
+        assert_eq!(first_leg.airline_numeric_code(), None);
+        assert_eq!(first_leg.unexpected_data(), None);
+        assert_eq!(first_leg.airline_individual_use(), Some("3JIJI7O4M28C"));
+    }
+}
+
+#[test]
+fn lossless_rewrite() {
+    let samples = [
+        // Lowercase sizes and the padding of the sections are kept as they are.
+        "M1MROZ/MARTIN         EXXXXXX SJCLAXAS 3317 207U001A0006 34d>218 VV8207BAS              2502771980993865 AS AS XXXXX55200000000Z29  00010",
+        "M1ASKREN/TEST         EA272SL ORDNRTUA 0881 007F002K0303 15C>3180 K6007BUA              2901624760758980 UA UA EY975897            *30600    09  UAG    ",
+        "M1TEST/PETER          E24Z5RN AMSBRUKL 1733 019M008A0001 316>503  W0D0742497067621",
+        "M1SOLLE/JOSUHUA       EQHSLJX ATLMEMDL 0254 006Y28C      10C3JIJI7O4M28C",
+        "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100^164GIWVC5EH7JNT684FVNJ91W2QA4DVN5J8K4F0L0GEQ3DF5TGBN8709HKT5D3DW3GBHFCVHMY7J5T6HFR41W2QA4DVN5J8K4F0L0GE",
+        "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 100^",
+        "M1DESMARAIS/LUC       EABC123 YULFRAAC 0834 326J001A0025 104>600^100",
+    ];
+
+    for src in samples {
+        let pass = Bcbp::from(src).unwrap();
+        assert_eq!(pass.to_string(), src);
+        assert_eq!(pass.clone().into_owned(), pass);
+    }
+
+    // The proxy rewrites the seat, leaving the carrier data as it is.
+    let src = "M1ASKREN/TEST         EA272SL ORDNRTUA 0881 007F002K0303 15C>3180 K6007BUA              2901624760758980 UA UA EY975897            *30600    09  UAG    ";
+    let mut pass = Bcbp::from(src).unwrap().into_owned();
+    pass.legs_mut()[0].set_seat("014C").unwrap();
+    pass.legs_mut()[0].set_frequent_flyer_number("EY1").unwrap();
+
+    let rewritten = pass.to_string();
+    assert_eq!(rewritten, src.replace("002K", "014C").replace("EY975897    ", "EY1         "));
+    assert_eq!(Bcbp::from(&rewritten).unwrap(), pass);
+    assert_eq!(pass.legs()[0].frequent_flyer_number(), Some("EY1             "));
+
+    let err = pass.legs_mut()[0].set_seat("0014C").unwrap_err();
+    assert_eq!(err.kind(),  ErrorKind::MandatoryDataSize);
+    assert_eq!(err.field(), Some(Field::SeatNumber));
+
+    // A conditional item the pass does not carry would move everything after it.
+    let err = pass.set_second_non_consecutive_baggage_tag_license_plate_numbers("0014123456003").unwrap_err();
+    assert_eq!(err.kind(),  ErrorKind::CoditionalData);
+    assert_eq!(err.field(), Some(Field::SecondNonConsecutiveBaggageTagLicensePlateNumbers));
+
+    assert_eq!(pass.legs_mut()[0].set_pnr("ÄBC").map_err(|e| e.kind()), Err(ErrorKind::InvalidCharacters));
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "with-security")]
+
+use iata::bcbp::{raw, AirlineDesignator, SecurityDataType};
+use iata::bcbp::security::{self, KeyError, KeyRegistry, PublicKey, Verification};
+
+const PAYLOAD: &str = "M1ASKREN/TEST         EA272SL ORDNRTUA 0881 007F002K0303 15C>3180 K6007BUA              2901624760758980 UA UA EY975897            *30600    09  UAG    ";
+
+const ECDSA_SIGNATURE: &str = "^160MEYCIQCIiSRpMWU6cCpEeNQKAenyFQIs1qtNJUmJX+gpMP9sgwIhAP5oynQ/xIZBGa8tvnfsh/2g1767Euq5u/Iag46n3HOz";
+
+const RSA_SIGNATURE: &str = "^1ACCh4W6TNdQdKcCHf6Fl2p4+fCJyTyKVkri5iGfF21GSGb2lyXWS4yspxBeBoYVcsyvspCUllAPtAvUIbPMCoFWYI3/7metknENxDuQ7cqGu9o+XAoACGhjuLcHhrNV4erpvdGHIHexF5dOeQjNfrp1SefxxE+fohdZANQdjIn+w8=";
+
+fn key_path(name: &str) -> String {
+    format!("{}/tests/keys/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn issuer(src: &str) -> AirlineDesignator {
+    src.parse().unwrap()
+}
+
+fn registry(key: &str) -> KeyRegistry {
+    let mut registry = KeyRegistry::new();
+    registry.load(issuer("UA"), SecurityDataType::DigitalSignature, key_path(key)).unwrap();
+    registry
+}
+
+#[test]
+fn payload() {
+    for signature in ["", "^", "^1", ECDSA_SIGNATURE, RSA_SIGNATURE] {
+        let src = format!("{}{}", PAYLOAD, signature);
+        let pass = raw::Bcbp::from(&src).unwrap();
+
+        assert_eq!(security::signed_payload(&pass), PAYLOAD);
+        assert_eq!(pass.to_string(), src);
+    }
+}
+
+#[test]
+fn verify_valid() {
+    let ecdsa = format!("{}{}", PAYLOAD, ECDSA_SIGNATURE);
+    let rsa = format!("{}{}", PAYLOAD, RSA_SIGNATURE);
+
+    let cases = [
+        ("ua_ecdsa_p256.pem", &ecdsa),
+        ("ua_rsa.der",        &rsa),
+        ("ua_rsa_pkcs1.pem",  &rsa),
+    ];
+
+    for (key, src) in cases {
+        let pass = raw::Bcbp::from(src).unwrap();
+        assert_eq!(registry(key).verify(&pass), Verification::Valid, "{}", key);
+    }
+}
+
+#[test]
+fn verify_invalid() {
+    let registry = registry("ua_ecdsa_p256.pem");
+
+    // The data was tampered with.
+    let src = format!("{}{}", PAYLOAD, ECDSA_SIGNATURE);
+    let mut pass = raw::Bcbp::from(&src).unwrap();
+    pass.legs_mut()[0].set_seat("001A").unwrap();
+    assert_eq!(registry.verify(&pass), Verification::Invalid);
+
+    // The signature was made with another key.
+    let src = format!("{}{}", PAYLOAD, RSA_SIGNATURE);
+    let pass = raw::Bcbp::from(&src).unwrap();
+    assert_eq!(registry.verify(&pass), Verification::Invalid);
+
+    // The signature is not Base64.
+    let src = format!("{}^104!!!!", PAYLOAD);
+    let pass = raw::Bcbp::from(&src).unwrap();
+    assert_eq!(registry.verify(&pass), Verification::Invalid);
+}
+
+#[test]
+fn verify_unknown_key() {
+    let src = format!("{}{}", PAYLOAD, ECDSA_SIGNATURE);
+    let pass = raw::Bcbp::from(&src).unwrap();
+
+    assert_eq!(KeyRegistry::new().verify(&pass), Verification::UnknownKey);
+
+    let key = PublicKey::from_file(key_path("ua_ecdsa_p256.pem")).unwrap();
+
+    let mut registry = KeyRegistry::new();
+    registry.register(issuer("LH"), SecurityDataType::DigitalSignature, key.clone());
+    registry.register(issuer("UA"), SecurityDataType::Other('2'), key);
+    assert_eq!(registry.verify(&pass), Verification::UnknownKey);
+
+    assert!(registry.get(issuer("LH"), SecurityDataType::DigitalSignature).is_some());
+    assert!(registry.get(issuer("UA"), SecurityDataType::DigitalSignature).is_none());
+}
+
+#[test]
+fn verify_unsigned() {
+    let registry = registry("ua_ecdsa_p256.pem");
+
+    for signature in ["", "^", "^1", "^100"] {
+        let src = format!("{}{}", PAYLOAD, signature);
+        let pass = raw::Bcbp::from(&src).unwrap();
+
+        assert_eq!(registry.verify(&pass), Verification::Unsigned, "{:?}", signature);
+    }
+}
+
+#[test]
+fn load_keys() {
+    let pem = std::fs::read_to_string(key_path("ua_ecdsa_p256.pem")).unwrap();
+    let der = std::fs::read(key_path("ua_rsa.der")).unwrap();
+
+    assert!(PublicKey::from_pem(&pem).is_ok());
+    assert!(PublicKey::from_der(&der).is_ok());
+
+    assert!(matches!(PublicKey::from_pem("-----BEGIN PUBLIC KEY-----"), Err(KeyError::UnsupportedKey)));
+    assert!(matches!(PublicKey::from_der(&der[1..]), Err(KeyError::UnsupportedKey)));
+    assert!(matches!(PublicKey::from_file(key_path("missing.pem")), Err(KeyError::Io(_))));
+}
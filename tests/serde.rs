@@ -0,0 +1,173 @@
+#![cfg(feature = "with-serde")]
+
+use iata::bcbp::{raw, Bcbp, CheckInSequence, FlightDesignator, Mode, Seat};
+use iata::datetime::*;
+use serde_json::{json, Value};
+
+const SAMPLES: &[&str] = &[
+    "M1BRUNER/ROMAN MR     EJNUFFX MUCSVOSU 2327 231L013A0052 100",
+    "M1TEST/HIDDEN         E8OQ6FU FRARLGLH 4010 012C004D0001 35C>2180WW6012BLH              2922023642241060 LH                        *30600000K09         ",
+    "M1ASKREN/TEST         EA272SL ORDNRTUA 0881 007F002K0303 15C>3180 K6007BUA              2901624760758980 UA UA EY975897            *30600    09  UAG    ",
+];
+
+#[test]
+fn owned_round_trip() {
+    for src in SAMPLES {
+        let pass = Bcbp::from(src).unwrap();
+        let json = serde_json::to_string(&pass).unwrap();
+
+        assert_eq!(serde_json::from_str::<Bcbp>(&json).unwrap(), pass, "{}", json);
+    }
+}
+
+#[test]
+fn owned_validation() {
+    let pass = Bcbp::from(SAMPLES[1]).unwrap();
+    let value = serde_json::to_value(&pass).unwrap();
+
+    let load = |path: &[&str], item: Value| {
+        let mut value = value.clone();
+        let mut target = &mut value;
+
+        for key in path {
+            target = match key.parse::<usize>() {
+                Ok(idx) => &mut target[idx],
+                Err(_)  => &mut target[*key],
+            };
+        }
+
+        *target = item;
+        serde_json::from_value::<Bcbp>(value)
+    };
+
+    assert!(load(&["legs", "0", "pnr"], json!("8OQ6FU")).is_ok());
+    assert!(load(&["legs", "0", "pnr"], json!("TOOLONGPNR")).is_err());
+    assert!(load(&["legs", "0", "flight_number"], json!("123456")).is_err());
+    assert!(load(&["legs", "0", "bag_allowance"], json!("1PCX")).is_err());
+    assert!(load(&["legs", "0", "selectee"], json!({ "Other": "\u{7}" })).is_err());
+    assert!(load(&["legs", "0", "src_airport"], json!("FR")).is_err());
+    assert!(load(&["legs", "0", "flight_day"], json!(400)).is_err());
+    assert!(load(&["legs", "0", "seat"], json!({ "Assigned": { "row": 0, "letter": "C" } })).is_err());
+    assert!(load(&["legs", "0", "sequence"], json!({ "Number": { "number": 100000, "suffix": null } })).is_err());
+    assert!(load(&["boardingpass_issued", "year_digit"], json!(12)).is_err());
+    assert!(load(&["legs", "0", "sequence"], json!({ "Other": "INFANT" })).is_err());
+
+    // Values outside the structured ones are parsed as the setters do.
+    let loaded = load(&["legs", "0", "seat"], json!({ "Other": "12C" })).unwrap();
+    assert_eq!(loaded.legs[0].seat(), Some(&Seat::new(12, 'C').unwrap()));
+
+    let loaded = load(&["legs", "0", "sequence"], json!({ "Other": "INF" })).unwrap();
+    assert_eq!(loaded.legs[0].sequence, Some(CheckInSequence::Other("INF".into())));
+
+    let error = load(&["legs", "0", "pnr"], json!("TOOLONGPNR")).unwrap_err();
+    assert!(error.to_string().contains("Operating Carrier PNR Code"), "{}", error);
+}
+
+#[test]
+fn flight_round_trip() {
+    for src in SAMPLES {
+        let flight = Bcbp::from(src).unwrap().legs[0].flight_designator().unwrap();
+        let json = serde_json::to_string(&flight).unwrap();
+
+        assert_eq!(serde_json::from_str::<FlightDesignator>(&json).unwrap(), flight, "{}", json);
+    }
+
+    let flight = FlightDesignator::new("LH", 400, Some('A')).unwrap();
+    let value = serde_json::to_value(flight).unwrap();
+    assert_eq!(value, json!({ "carrier": "LH", "number": 400, "suffix": "A" }));
+    assert_eq!(serde_json::from_value::<FlightDesignator>(value).unwrap(), flight);
+
+    assert!(serde_json::from_value::<FlightDesignator>(json!({ "carrier": "L", "number": 400, "suffix": null })).is_err());
+    assert!(serde_json::from_value::<FlightDesignator>(json!({ "carrier": "LH", "number": 0, "suffix": null })).is_err());
+    assert!(serde_json::from_value::<FlightDesignator>(json!({ "carrier": "LH", "number": 400, "suffix": "a" })).is_err());
+}
+
+#[test]
+fn datetime_round_trip() {
+    let date = ShortDate::new(Month::February, 29).unwrap();
+    let time = Time::new(20, 45, Some(12), TzTag::Utc).unwrap();
+    let datetime = ShortDateTime::new(date.clone(), time.clone());
+    let issued = JulianIssueDate::new(6, DayOfYear::new(12).unwrap()).unwrap();
+
+    let json = serde_json::to_string(&datetime).unwrap();
+    assert_eq!(serde_json::from_str::<ShortDateTime>(&json).unwrap(), datetime);
+
+    let json = serde_json::to_string(&issued).unwrap();
+    assert_eq!(serde_json::from_str::<JulianIssueDate>(&json).unwrap(), issued);
+
+    assert_eq!(serde_json::to_value(TzTag::None).unwrap(), json!("None"));
+    assert_eq!(serde_json::from_value::<TzTag>(json!("Local")).unwrap(), TzTag::Local);
+
+    assert!(serde_json::from_value::<DayOfYear>(json!(0)).is_err());
+    assert!(serde_json::from_value::<ShortDate>(json!({ "month": "February", "day": 30 })).is_err());
+    assert!(serde_json::from_value::<ShortDate>(json!({ "month": "Brumaire", "day": 1 })).is_err());
+    assert!(serde_json::from_value::<Time>(json!({
+        "hour": 24, "minute": 0, "second": null, "timezone": "None"
+    })).is_err());
+    assert!(serde_json::from_value::<Time>(json!({
+        "hour": 23, "minute": 59, "second": 60, "timezone": "Utc"
+    })).is_err());
+}
+
+#[test]
+fn raw_serialize() {
+    let pass = raw::Bcbp::from(SAMPLES[1]).unwrap();
+    let value = serde_json::to_value(&pass).unwrap();
+
+    assert!(value.get("src").is_none());
+    assert_eq!(value["pax_name"], json!("TEST/HIDDEN         "));
+    assert_eq!(value["version"], json!("V2"));
+    assert_eq!(value["unique_size"], json!("18"));
+    assert_eq!(value["legs"][0]["seat"], json!("004D"));
+    assert_eq!(value["legs"][0]["marketing_carrier_designator"], json!("LH "));
+    assert_eq!(value["security_data"]["present"], json!(false));
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Trip {
+    #[serde(with = "iata::bcbp::serde_as_string")]
+    pass: Bcbp,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct StrictTrip {
+    #[serde(with = "iata::bcbp::serde_as_string::strict")]
+    pass: Bcbp,
+}
+
+#[test]
+fn as_string() {
+    for src in SAMPLES {
+        let trip = Trip { pass: Bcbp::from(src).unwrap() };
+        let value = serde_json::to_value(&trip).unwrap();
+
+        assert_eq!(value, json!({ "pass": trip.pass.build(Mode::Tolerant).unwrap() }));
+        assert_eq!(serde_json::from_value::<Trip>(value).unwrap(), trip);
+    }
+
+    let trip: Trip = serde_json::from_value(json!({ "pass": SAMPLES[0] })).unwrap();
+    assert_eq!(serde_json::to_value(&trip).unwrap(), json!({ "pass": SAMPLES[0] }));
+
+    assert!(serde_json::from_value::<Trip>(json!({ "pass": "M1BRUNER" })).is_err());
+    assert!(serde_json::from_value::<Trip>(json!({ "pass": 1 })).is_err());
+}
+
+#[test]
+fn as_string_with_mode() {
+    let src = "M1Bruner/Roman Mr     EJNUFFX MUCSVOSU 2327 231L013A0052 100";
+
+    assert!(serde_json::from_value::<Trip>(json!({ "pass": src })).is_ok());
+    assert!(serde_json::from_value::<StrictTrip>(json!({ "pass": src })).is_err());
+
+    let trip = StrictTrip { pass: Bcbp::from(SAMPLES[1]).unwrap() };
+    let value = serde_json::to_value(&trip).unwrap();
+    assert_eq!(value, json!({ "pass": trip.pass.build(Mode::Strict).unwrap() }));
+    assert_eq!(serde_json::from_value::<StrictTrip>(value).unwrap(), trip);
+
+    // Strict building requires every mandatory item.
+    let mut pass = Bcbp::from(SAMPLES[0]).unwrap();
+    pass.legs[0].set_pnr("").unwrap();
+
+    assert!(serde_json::to_value(Trip { pass: pass.clone() }).is_ok());
+    assert!(serde_json::to_value(StrictTrip { pass }).is_err());
+}